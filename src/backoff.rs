@@ -0,0 +1,148 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::{text::Line, DefaultTerminal};
+
+use crate::{parse_or_default, prompt, State};
+
+const MAX_ATTEMPTS: u32 = 30;
+const MAX_CLIENTS: u64 = 100_000;
+const MAX_DELAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+const HISTOGRAM_BUCKETS: u64 = 20;
+const HISTOGRAM_WIDTH: u64 = 40;
+
+// the three strategies from the AWS architecture blog "Exponential Backoff And Jitter",
+// plus no jitter at all for comparison
+#[derive(Clone, Copy)]
+pub enum Jitter {
+    None,
+    Full,
+    Equal,
+    Decorrelated,
+}
+
+impl Jitter {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "none" => Ok(Jitter::None),
+            "" | "full" => Ok(Jitter::Full),
+            "equal" => Ok(Jitter::Equal),
+            "decorrelated" | "decorr" => Ok(Jitter::Decorrelated),
+            other => Err(format!("unknown jitter \"{}\", expected none, full, equal or decorrelated", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Jitter::None => "no",
+            Jitter::Full => "full",
+            Jitter::Equal => "equal",
+            Jitter::Decorrelated => "decorrelated",
+        }
+    }
+}
+
+// min(cap, base * 2^attempt) without overflowing for large attempt numbers
+fn exponential(base: u64, cap: u64, attempt: u32) -> u64 {
+    base.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX)).min(cap)
+}
+
+// delays in milliseconds before each retry
+pub fn schedule(rng: &mut ChaCha20Rng, jitter: Jitter, base: u64, cap: u64, attempts: u32) -> Vec<u64> {
+    let mut delays = Vec::with_capacity(attempts as usize);
+    let mut previous = base;
+    for attempt in 0..attempts {
+        let delay = match jitter {
+            Jitter::None => exponential(base, cap, attempt),
+            Jitter::Full => rng.random_range(0..=exponential(base, cap, attempt)),
+            Jitter::Equal => {
+                let ceiling = exponential(base, cap, attempt);
+                ceiling / 2 + rng.random_range(0..=ceiling - ceiling / 2)
+            }
+            // sleep = min(cap, random_between(base, sleep * 3)), carried over from the last attempt
+            Jitter::Decorrelated => rng.random_range(base..=previous.saturating_mul(3).max(base)).min(cap),
+        };
+        previous = delay;
+        delays.push(delay);
+    }
+    delays
+}
+
+fn format_millis(millis: u64) -> String {
+    if millis < 1000 {
+        format!("{}ms", millis)
+    } else {
+        format!("{:.2}s", millis as f64 / 1000.0)
+    }
+}
+
+// every client fails at time zero and retries on its own schedule; the histogram shows how
+// the retries land on the server over time
+fn simulate(state: &mut State, rng: &mut ChaCha20Rng, jitter: Jitter, base: u64, cap: u64, attempts: u32, clients: u64) {
+    let mut retry_times = Vec::with_capacity((clients * attempts as u64) as usize);
+    for _ in 0..clients {
+        let mut elapsed = 0u64;
+        for delay in schedule(rng, jitter, base, cap, attempts) {
+            elapsed = elapsed.saturating_add(delay);
+            retry_times.push(elapsed);
+        }
+    }
+    let span = retry_times.iter().copied().max().unwrap_or(0).max(1);
+    let bucket_width = span.div_ceil(HISTOGRAM_BUCKETS).max(1);
+    let mut buckets = vec![0u64; HISTOGRAM_BUCKETS as usize];
+    for time in &retry_times {
+        buckets[((time / bucket_width) as usize).min(HISTOGRAM_BUCKETS as usize - 1)] += 1;
+    }
+    let peak = buckets.iter().copied().max().unwrap_or(0).max(1);
+
+    state.push_message_output(format!(
+        "{} clients, {} jitter: {} retries over {}, busiest {} window has {} ({:.1}% of all calls)",
+        clients, jitter.name(), retry_times.len(), format_millis(span), format_millis(bucket_width), peak,
+        peak as f64 * 100.0 / retry_times.len().max(1) as f64
+    ));
+    let lines = buckets.iter().enumerate()
+        .map(|(index, count)| {
+            let bar = "█".repeat((count * HISTOGRAM_WIDTH).div_ceil(peak) as usize);
+            Line::from(format!("    {:>9} {:<40} {}", format_millis(index as u64 * bucket_width), bar, count))
+        })
+        .collect();
+    state.push_output_lines(lines);
+}
+
+fn retry_schedule(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let jitter = Jitter::parse(&prompt(terminal, state, "Jitter: none, full, equal, decorrelated (blank for full)")?)?;
+    let base = parse_or_default(&prompt(terminal, state, "Base delay in ms (blank for 100)")?, 100, 1..=MAX_DELAY_MILLIS, "base")?;
+    let cap = parse_or_default(&prompt(terminal, state, "Cap in ms (blank for 20000)")?, 20_000, 1..=MAX_DELAY_MILLIS, "cap")?;
+    if base > cap {
+        return Err(String::from("base must be no more than the cap"));
+    }
+    let attempts = parse_or_default(&prompt(terminal, state, "Attempts (blank for 8)")?, 8, 1..=MAX_ATTEMPTS, "attempts")?;
+
+    let input = prompt(terminal, state, "Clients to simulate (blank for a single schedule)")?;
+    let clients = parse_or_default(&input, 1, 1..=MAX_CLIENTS, "clients")?;
+    if clients > 1 {
+        simulate(state, rng, jitter, base, cap, attempts, clients);
+        return Ok(());
+    }
+
+    let delays = schedule(rng, jitter, base, cap, attempts);
+    let total = delays.iter().fold(0u64, |total, delay| total.saturating_add(*delay));
+    state.push_message_output(format!(
+        "{} jitter, base {}, cap {}: {} retries over {}",
+        jitter.name(), format_millis(base), format_millis(cap), attempts, format_millis(total)
+    ));
+    let mut elapsed = 0u64;
+    let lines = delays.iter().enumerate()
+        .map(|(attempt, delay)| {
+            elapsed = elapsed.saturating_add(*delay);
+            Line::from(format!("    retry {:>2}: wait {:>8}  (at {})", attempt + 1, format_millis(*delay), format_millis(elapsed)))
+        })
+        .collect();
+    state.push_output_lines(lines);
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = retry_schedule(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
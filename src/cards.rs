@@ -0,0 +1,279 @@
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha20Rng;
+use ratatui::{
+    style::{Color, Stylize},
+    text::Span,
+    DefaultTerminal,
+};
+
+use crate::{parse_or_default, prompt, State};
+
+const MAX_DECKS: usize = 16;
+const RANKS: [&str; 13] = ["A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K"];
+const TAROT_MAJOR_ARCANA: [&str; 22] = [
+    "The Fool", "The Magician", "The High Priestess", "The Empress", "The Emperor", "The Hierophant",
+    "The Lovers", "The Chariot", "Strength", "The Hermit", "Wheel of Fortune", "Justice", "The Hanged Man",
+    "Death", "Temperance", "The Devil", "The Tower", "The Star", "The Moon", "The Sun", "Judgement", "The World",
+];
+const TAROT_RANKS: [&str; 14] = [
+    "Ace", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Page", "Knight", "Queen", "King",
+];
+
+#[derive(Clone, Copy)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+#[derive(Clone, Copy)]
+pub enum TarotSuit {
+    Wands,
+    Cups,
+    Swords,
+    Pentacles,
+}
+
+#[derive(Clone)]
+pub enum Card {
+    Standard { rank: usize, suit: Suit }, // rank indexes into RANKS
+    Joker { red: bool },
+    MajorArcana(usize),
+    MinorArcana { rank: usize, suit: TarotSuit }, // rank indexes into TAROT_RANKS
+    Custom(String),
+}
+
+impl Suit {
+    const ALL: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Suit::Spades => "♠",
+            Suit::Hearts => "♥",
+            Suit::Diamonds => "♦",
+            Suit::Clubs => "♣",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Suit::Hearts | Suit::Diamonds => Color::LightRed,
+            Suit::Spades | Suit::Clubs => Color::White,
+        }
+    }
+}
+
+impl TarotSuit {
+    const ALL: [TarotSuit; 4] = [TarotSuit::Wands, TarotSuit::Cups, TarotSuit::Swords, TarotSuit::Pentacles];
+
+    fn name(self) -> &'static str {
+        match self {
+            TarotSuit::Wands => "Wands",
+            TarotSuit::Cups => "Cups",
+            TarotSuit::Swords => "Swords",
+            TarotSuit::Pentacles => "Pentacles",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            TarotSuit::Wands => Color::LightRed,
+            TarotSuit::Cups => Color::LightBlue,
+            TarotSuit::Swords => Color::Gray,
+            TarotSuit::Pentacles => Color::Yellow,
+        }
+    }
+}
+
+impl Card {
+    pub fn label(&self) -> String {
+        match self {
+            Card::Standard { rank, suit } => format!("{}{}", RANKS[*rank], suit.symbol()),
+            Card::Joker { red: true } => String::from("Red Joker"),
+            Card::Joker { red: false } => String::from("Black Joker"),
+            Card::MajorArcana(index) => TAROT_MAJOR_ARCANA[*index].to_string(),
+            Card::MinorArcana { rank, suit } => format!("{} of {}", TAROT_RANKS[*rank], suit.name()),
+            Card::Custom(name) => name.clone(),
+        }
+    }
+
+    pub fn span(&self) -> Span<'static> {
+        let span = Span::raw(self.label());
+        match self {
+            Card::Standard { suit, .. } => span.fg(suit.color()).bold(),
+            Card::Joker { .. } | Card::MajorArcana(_) => span.fg(Color::Magenta).bold(),
+            Card::MinorArcana { suit, .. } => span.fg(suit.color()),
+            Card::Custom(_) => span,
+        }
+    }
+}
+
+pub enum DeckKind {
+    Standard,
+    WithJokers,
+    Tarot,
+    Custom(Vec<String>),
+}
+
+impl DeckKind {
+    // "standard", "jokers", "tarot", or a path to a file with one card per line
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "" | "standard" | "52" => Ok(DeckKind::Standard),
+            "jokers" | "standard+jokers" | "54" => Ok(DeckKind::WithJokers),
+            "tarot" => Ok(DeckKind::Tarot),
+            _ => {
+                let path = input.trim();
+                let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+                let cards: Vec<String> = contents.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(String::from)
+                    .collect();
+                if cards.is_empty() {
+                    return Err(format!("{}: no cards found", path));
+                }
+                Ok(DeckKind::Custom(cards))
+            }
+        }
+    }
+
+    fn cards(&self) -> Vec<Card> {
+        match self {
+            DeckKind::Standard | DeckKind::WithJokers => {
+                let mut cards: Vec<Card> = Suit::ALL.iter()
+                    .flat_map(|&suit| (0..RANKS.len()).map(move |rank| Card::Standard { rank, suit }))
+                    .collect();
+                if let DeckKind::WithJokers = self {
+                    cards.push(Card::Joker { red: true });
+                    cards.push(Card::Joker { red: false });
+                }
+                cards
+            }
+            DeckKind::Tarot => {
+                let mut cards: Vec<Card> = (0..TAROT_MAJOR_ARCANA.len()).map(Card::MajorArcana).collect();
+                cards.extend(TarotSuit::ALL.iter()
+                    .flat_map(|&suit| (0..TAROT_RANKS.len()).map(move |rank| Card::MinorArcana { rank, suit })));
+                cards
+            }
+            DeckKind::Custom(names) => names.iter().cloned().map(Card::Custom).collect(),
+        }
+    }
+}
+
+// the remaining cards, kept in State so draws carry over between menu actions
+pub struct Deck {
+    pub cards: Vec<Card>, // top of the deck is the end of the vec
+    pub total: usize,
+}
+
+impl Deck {
+    pub fn new(kind: &DeckKind, decks: usize) -> Self {
+        let cards: Vec<Card> = (0..decks).flat_map(|_| kind.cards()).collect();
+        let total = cards.len();
+        Self { cards, total }
+    }
+
+    pub fn shuffle(&mut self, rng: &mut ChaCha20Rng) {
+        self.cards.shuffle(rng);
+    }
+
+    pub fn draw(&mut self, count: usize) -> Result<Vec<Card>, String> {
+        if count > self.cards.len() {
+            return Err(format!("only {} cards left in the deck", self.cards.len()));
+        }
+        let split = self.cards.len() - count;
+        let mut drawn = self.cards.split_off(split);
+        drawn.reverse();
+        Ok(drawn)
+    }
+
+    // deals one card at a time round the table, like a real deal
+    pub fn deal(&mut self, players: usize, per_player: usize) -> Result<Vec<Vec<Card>>, String> {
+        let needed = players.checked_mul(per_player).ok_or("too many cards requested")?;
+        let drawn = self.draw(needed)?;
+        let mut hands = vec![Vec::with_capacity(per_player); players];
+        for (index, card) in drawn.into_iter().enumerate() {
+            hands[index % players].push(card);
+        }
+        Ok(hands)
+    }
+}
+
+fn card_spans(prefix: String, cards: &[Card]) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::raw(prefix)];
+    for (index, card) in cards.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(", "));
+        }
+        spans.push(card.span());
+    }
+    spans
+}
+
+fn new_deck(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let kind_input = prompt(terminal, state, "Deck (standard, jokers, tarot, or file path)")?;
+    let kind = DeckKind::parse(&kind_input)?;
+    let decks = parse_or_default(&prompt(terminal, state, "Number of decks (blank for 1)")?, 1, 1..=MAX_DECKS, "decks")?;
+
+    let mut deck = Deck::new(&kind, decks);
+    deck.shuffle(rng);
+    state.push_message_output(format!("new shuffled deck of {} cards", deck.total));
+    state.card_deck = Some(deck);
+    Ok(())
+}
+
+fn card_action(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let input = prompt(terminal, state, "Cards: new | shuffle | draw [n] | deal <players> [cards] | left")?;
+    let mut words = input.split_whitespace();
+    let action = words.next().unwrap_or("draw").to_lowercase();
+
+    if action == "new" || state.card_deck.is_none() {
+        if action != "new" {
+            state.push_message_output(String::from("no deck yet, creating one"));
+        }
+        return new_deck(terminal, state, rng);
+    }
+    let Some(deck) = state.card_deck.as_mut() else {
+        return Ok(());
+    };
+
+    match action.as_str() {
+        "shuffle" => {
+            // shuffles only what is left, drawn cards stay out of play
+            deck.shuffle(rng);
+            let message = format!("shuffled the {} remaining cards", deck.cards.len());
+            state.push_message_output(message);
+        }
+        "draw" => {
+            // nothing can ask for more cards than the full deck, draw/deal report what's actually left
+            let count = parse_or_default(words.next().unwrap_or(""), 1, 1..=deck.total, "count")?;
+            let drawn = deck.draw(count)?;
+            let prefix = format!("drew {} ({} left): ", drawn.len(), deck.cards.len());
+            state.push_styled_output(card_spans(prefix, &drawn));
+        }
+        "deal" => {
+            let players = parse_or_default(words.next().unwrap_or(""), 2, 1..=deck.total, "players")?;
+            let per_player = parse_or_default(words.next().unwrap_or(""), 5, 1..=deck.total, "cards")?;
+            let hands = deck.deal(players, per_player)?;
+            let left = deck.cards.len();
+            for (index, hand) in hands.iter().enumerate() {
+                state.push_styled_output(card_spans(format!("player {}: ", index + 1), hand));
+            }
+            state.push_message_output(format!("{} cards left in the deck", left));
+        }
+        "left" => {
+            let message = format!("{} of {} cards left in the deck", deck.cards.len(), deck.total);
+            state.push_message_output(message);
+        }
+        _ => return Err(format!("unknown card action \"{}\"", action)),
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = card_action(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
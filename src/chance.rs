@@ -0,0 +1,109 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{parse_or_default, prompt, State};
+
+// anything above this makes the UI hang for a noticeable amount of time
+const MAX_TRIALS: u64 = 10_000_000;
+
+pub struct TrialSummary {
+    pub trials: u64,
+    pub hits: u64,
+    pub first_hit: Option<u64>, // 1-based attempt number of the first hit
+}
+
+// parses a percentage in the 0-100 range and returns it as a probability (0.0-1.0)
+pub fn parse_percentage(input: &str) -> Result<f64, String> {
+    let value = input.trim().trim_end_matches('%').trim().parse::<f64>().map_err(|e| e.to_string())?;
+    if !value.is_finite() || !(0.0..=100.0).contains(&value) {
+        return Err(format!("percentage must be between 0 and 100, got {}", value));
+    }
+    Ok(value / 100.0)
+}
+
+pub fn run_trials(rng: &mut ChaCha20Rng, probability: f64, trials: u64) -> TrialSummary {
+    let mut summary = TrialSummary { trials, hits: 0, first_hit: None };
+    for attempt in 1..=trials {
+        if rng.random_bool(probability) {
+            summary.hits += 1;
+            if summary.first_hit.is_none() {
+                summary.first_hit = Some(attempt);
+            }
+        }
+    }
+    summary
+}
+
+// P(at least one hit in n tries) = 1 - (1 - p)^n
+pub fn at_least_one_hit(probability: f64, trials: u64) -> f64 {
+    1.0 - (1.0 - probability).powf(trials as f64)
+}
+
+// mean of the geometric distribution, None when a hit can never happen
+pub fn expected_attempts(probability: f64) -> Option<f64> {
+    if probability > 0.0 {
+        Some(1.0 / probability)
+    } else {
+        None
+    }
+}
+
+// smallest n where P(at least one hit in n tries) reaches the target confidence
+pub fn attempts_for_confidence(probability: f64, confidence: f64) -> Option<u64> {
+    if probability <= 0.0 {
+        return None;
+    }
+    if probability >= 1.0 {
+        return Some(1);
+    }
+    Some(((1.0 - confidence).ln() / (1.0 - probability).ln()).ceil() as u64)
+}
+
+fn roll(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let probability = parse_percentage(&prompt(terminal, state, "Enter percentage (0-100)")?)?;
+    // blank means a single roll, like the old behaviour
+    let trials = parse_or_default(&prompt(terminal, state, "Enter number of trials (blank for 1)")?, 1, 1..=MAX_TRIALS, "trials")?;
+
+    let summary = run_trials(rng, probability, trials);
+    if summary.trials == 1 {
+        state.push_message_output(format!("Hit: {}", summary.hits == 1));
+        return Ok(());
+    }
+
+    let misses = summary.trials - summary.hits;
+    let observed_rate = summary.hits as f64 / summary.trials as f64;
+    state.push_message_output(format!(
+        "{} trials at {}%: {} hits, {} misses",
+        summary.trials, probability * 100.0, summary.hits, misses
+    ));
+    state.push_message_output(format!(
+        "hit rate: simulated {:.4}% vs theory {:.4}%",
+        observed_rate * 100.0, probability * 100.0
+    ));
+    state.push_message_output(format!(
+        "P(at least one hit in {} tries): {:.4}%",
+        summary.trials, at_least_one_hit(probability, summary.trials) * 100.0
+    ));
+
+    let first_hit = match summary.first_hit {
+        Some(attempt) => attempt.to_string(),
+        None => String::from("never"),
+    };
+    match expected_attempts(probability) {
+        Some(expected) => state.push_message_output(format!(
+            "attempts until first hit: simulated {} vs expected {:.2}", first_hit, expected
+        )),
+        None => state.push_message_output(String::from("attempts until first hit: never (0% chance)")),
+    }
+    if let Some(attempts) = attempts_for_confidence(probability, 0.9) {
+        state.push_message_output(format!("tries needed for 90% chance of a hit: {}", attempts));
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = roll(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
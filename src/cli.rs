@@ -0,0 +1,177 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use crate::{encoding::Encoding, export, fake, lorem, tokens};
+
+const USAGE: &str = "\
+usage: rsrand [command] [options]
+
+with no command rsrand starts the interactive menu
+
+commands:
+  token    random bytes for keys and secrets
+           --bytes N          number of random bytes (default 32)
+           --encoding NAME    hex, base64, base64url, base32, crockford, base58, z85 or raw (default hex)
+           --output FILE      write to FILE instead of stdout
+  export   bulk fake records from a schema file, streamed row by row
+           --schema FILE      columns and their generators (.toml or .json)
+           --rows N           number of rows (default 1000)
+           --format NAME      csv, jsonl or sql (default csv)
+           --output FILE      write to FILE instead of stdout
+           --seed SEED        reproducible output for the same seed
+  lorem    placeholder text, one paragraph unless a count is given (at most 100000)
+           --words N          N words
+           --sentences N      N sentences
+           --paragraphs N     N paragraphs separated by blank lines
+           --source NAME      lorem or words for the passphrase wordlist (default lorem)
+           --output FILE      write to FILE instead of stdout
+           --seed SEED        reproducible output for the same seed
+  help     show this message";
+
+// encoded output is built in memory, raw output is streamed so this only limits text encodings
+const MAX_ENCODED_BYTES: u64 = 16 * 1024 * 1024;
+const RAW_CHUNK_BYTES: usize = 64 * 1024;
+// lorem text is also built in memory, 100k paragraphs is already around 50 MB
+const MAX_LOREM_COUNT: u64 = 100_000;
+
+// pulls `--name value` pairs out of the argument list, rejecting anything unknown
+pub struct Options {
+    pairs: Vec<(String, String)>,
+}
+
+impl Options {
+    pub fn parse(args: &[String], allowed: &[&str]) -> Result<Self, String> {
+        let mut pairs = Vec::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                return Err(format!("unexpected argument \"{}\"", arg));
+            };
+            if !allowed.contains(&name) {
+                return Err(format!("unknown option --{}", name));
+            }
+            let value = iter.next().ok_or_else(|| format!("--{} needs a value", name))?;
+            pairs.push((name.to_string(), value.clone()));
+        }
+        Ok(Self { pairs })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs.iter().rev().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    pub fn number(&self, name: &str, default: u64) -> Result<u64, String> {
+        match self.get(name) {
+            None => Ok(default),
+            Some(value) => value.parse::<u64>().map_err(|e| format!("--{} {}: {}", name, value, e)),
+        }
+    }
+}
+
+pub fn open_output(path: Option<&str>) -> Result<Box<dyn Write>, String> {
+    match path {
+        None | Some("-") => Ok(Box::new(BufWriter::new(io::stdout().lock()))),
+        Some(path) => {
+            let file = File::create(path).map_err(|e| format!("{}: {}", path, e))?;
+            Ok(Box::new(BufWriter::new(file)))
+        }
+    }
+}
+
+fn token(args: &[String], rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let options = Options::parse(args, &["bytes", "encoding", "output"])?;
+    let count = options.number("bytes", tokens::DEFAULT_BYTES as u64)?;
+    if count == 0 {
+        return Err(String::from("--bytes must be at least 1"));
+    }
+    // everything is validated before the output is opened, opening truncates an existing file
+    let encoding = match options.get("encoding").unwrap_or("hex") {
+        "raw" | "binary" => None,
+        name => Some(Encoding::parse(name)?),
+    };
+    if let Some(encoding) = encoding {
+        if count > MAX_ENCODED_BYTES {
+            return Err(format!("--bytes is limited to {} for text encodings, use raw for more", MAX_ENCODED_BYTES));
+        }
+        encoding.check_length(count as usize)?;
+    }
+    let mut output = open_output(options.get("output"))?;
+
+    match encoding {
+        None => {
+            let mut remaining = count;
+            while remaining > 0 {
+                let chunk = remaining.min(RAW_CHUNK_BYTES as u64) as usize;
+                output.write_all(&tokens::random_bytes(rng, chunk)).map_err(|e| e.to_string())?;
+                remaining -= chunk as u64;
+            }
+        }
+        Some(encoding) => {
+            let encoded = encoding.encode(&tokens::random_bytes(rng, count as usize))?;
+            writeln!(output, "{}", encoded).map_err(|e| e.to_string())?;
+        }
+    }
+    output.flush().map_err(|e| e.to_string())
+}
+
+fn export(args: &[String], rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let options = Options::parse(args, &["schema", "rows", "format", "output", "seed"])?;
+    let schema = options.get("schema").ok_or("--schema is required")?;
+    let rows = options.number("rows", 1000)?;
+    let format = export::Format::parse(options.get("format").unwrap_or("csv"))?;
+
+    let mut seeded = options.get("seed").map(fake::seeded_rng);
+    let rng = seeded.as_mut().unwrap_or(rng);
+    export::export(schema, rows, format, options.get("output"), rng)
+}
+
+fn lorem(args: &[String], rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let options = Options::parse(args, &["words", "sentences", "paragraphs", "source", "output", "seed"])?;
+    let units: Vec<&str> = ["words", "sentences", "paragraphs"].into_iter().filter(|unit| options.get(unit).is_some()).collect();
+    let (unit, count) = match units.as_slice() {
+        [] => (lorem::Unit::Paragraphs, 1),
+        [unit] => (lorem::Unit::parse(unit)?, options.number(unit, 1)?),
+        _ => return Err(String::from("use only one of --words, --sentences and --paragraphs")),
+    };
+    if count == 0 || count > MAX_LOREM_COUNT {
+        return Err(format!("the count must be between 1 and {}", MAX_LOREM_COUNT));
+    }
+    let source = lorem::Source::parse(options.get("source").unwrap_or("lorem"))?;
+
+    let mut seeded = options.get("seed").map(fake::seeded_rng);
+    let rng = seeded.as_mut().unwrap_or(rng);
+    let separator = if let lorem::Unit::Paragraphs = unit { "\n\n" } else { "\n" };
+    let mut output = open_output(options.get("output"))?;
+    writeln!(output, "{}", lorem::generate(rng, source, unit, count as usize).join(separator)).map_err(|e| e.to_string())?;
+    output.flush().map_err(|e| e.to_string())
+}
+
+// headless entry point, returns the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let mut rng = ChaCha20Rng::from_os_rng();
+    let (command, rest) = args.split_first().expect("run is only called with arguments");
+
+    let result = match command.as_str() {
+        "token" => token(rest, &mut rng),
+        "export" => export(rest, &mut rng),
+        "lorem" => lorem(rest, &mut rng),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("unknown command \"{}\"\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("rsrand: {}", error);
+            2
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{codename_corpus::*, prompt, prompt_count, State};
+
+const MAX_COUNT: usize = 16;
+const MAX_ATTEMPTS: u32 = 10_000;
+
+#[derive(Clone, Copy)]
+pub enum Case {
+    Lower,
+    Upper,
+    // braveOtter
+    Camel,
+    // BraveOtter
+    Pascal,
+}
+
+pub struct Options {
+    pub alliterate: bool,
+    // digits in the number suffix, 0 for none
+    pub digits: u32,
+    pub separator: String,
+    pub case: Case,
+}
+
+impl Options {
+    // space separated: alliterate, number[=digits], sep=_, case=kebab|snake|camel|pascal|upper
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut options = Options { alliterate: false, digits: 0, separator: String::from("-"), case: Case::Lower };
+        let mut separator = None;
+        for option in input.split_whitespace() {
+            match option.split_once('=') {
+                None if option == "alliterate" => options.alliterate = true,
+                None if option == "number" => options.digits = 2,
+                Some(("number", digits)) => {
+                    options.digits = digits.parse::<u32>().map_err(|e| format!("number digits: {}", e))?;
+                    if options.digits > 9 {
+                        return Err(String::from("the number suffix is limited to 9 digits"));
+                    }
+                }
+                Some(("sep", value)) => separator = Some(value.to_string()),
+                Some(("case", "kebab")) => (options.case, options.separator) = (Case::Lower, String::from("-")),
+                Some(("case", "snake")) => (options.case, options.separator) = (Case::Lower, String::from("_")),
+                Some(("case", "upper")) => (options.case, options.separator) = (Case::Upper, String::from("_")),
+                Some(("case", "camel")) => (options.case, options.separator) = (Case::Camel, String::new()),
+                Some(("case", "pascal")) => (options.case, options.separator) = (Case::Pascal, String::new()),
+                _ => return Err(format!("unknown option \"{}\"", option)),
+            }
+        }
+        // an explicit separator wins over the one implied by the case style
+        if let Some(separator) = separator {
+            options.separator = separator;
+        }
+        Ok(options)
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut characters = word.chars();
+    characters.next().map(|first| first.to_uppercase().chain(characters).collect()).unwrap_or_default()
+}
+
+// lowercase with separators stripped, so brave-otter, brave_otter and BraveOtter all collide
+fn normalize(name: &str) -> String {
+    name.chars().filter(|character| character.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+pub fn generate(rng: &mut ChaCha20Rng, options: &Options) -> String {
+    let adjective = ADJECTIVES[rng.random_range(0..ADJECTIVES.len())];
+    let noun = if options.alliterate {
+        let matching: Vec<&str> = NOUNS.iter().copied().filter(|noun| noun.as_bytes()[0] == adjective.as_bytes()[0]).collect();
+        matching[rng.random_range(0..matching.len())]
+    } else {
+        NOUNS[rng.random_range(0..NOUNS.len())]
+    };
+
+    let mut parts: Vec<String> = vec![adjective.to_string(), noun.to_string()];
+    if options.digits > 0 {
+        parts.push(rng.random_range(10u64.pow(options.digits - 1)..10u64.pow(options.digits)).to_string());
+    }
+    let parts: Vec<String> = match options.case {
+        Case::Lower => parts,
+        Case::Upper => parts.iter().map(|part| part.to_uppercase()).collect(),
+        Case::Camel => parts.iter().enumerate().map(|(index, part)| if index == 0 { part.clone() } else { capitalize(part) }).collect(),
+        Case::Pascal => parts.iter().map(|part| capitalize(part)).collect(),
+    };
+    parts.join(&options.separator)
+}
+
+// one name per line, blank lines and # comments are skipped
+pub fn load_used(path: &str) -> Result<HashSet<String>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(contents.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(normalize)
+        .collect())
+}
+
+// fresh names that don't clash with the used list or with each other
+pub fn generate_unique(rng: &mut ChaCha20Rng, options: &Options, used: &mut HashSet<String>, count: usize) -> Result<Vec<String>, String> {
+    let mut names = Vec::with_capacity(count);
+    let mut attempts = 0;
+    while names.len() < count {
+        let name = generate(rng, options);
+        if used.insert(normalize(&name)) {
+            names.push(name);
+            continue;
+        }
+        attempts += 1;
+        if attempts >= MAX_ATTEMPTS {
+            return Err(String::from("ran out of unused names, try a number suffix"));
+        }
+    }
+    Ok(names)
+}
+
+fn generate_codenames(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let options = Options::parse(&prompt(terminal, state, "Options: alliterate number[=digits] sep=- case=kebab|snake|camel|pascal|upper")?)?;
+
+    let path = prompt(terminal, state, "File of used names to avoid (blank for none)")?;
+    let mut used = match path.trim() {
+        "" => HashSet::new(),
+        path => load_used(path)?,
+    };
+
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for name in generate_unique(rng, &options, &mut used, count)? {
+        state.push_message_output(name);
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_codenames(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
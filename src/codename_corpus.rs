@@ -0,0 +1,59 @@
+// kept apart from words.rs, passphrase words are picked for entropy and these for sounding like names;
+// every starting letter appears in both lists so alliteration always has something to pick
+pub static ADJECTIVES: &[&str] = &[
+    "able", "agile", "amber", "ancient", "arctic", "azure",
+    "bold", "brave", "bright", "brisk", "bronze", "busy",
+    "calm", "clever", "cosmic", "crimson", "crisp", "curious",
+    "daring", "dashing", "dizzy", "dreamy", "dusty", "dynamic",
+    "eager", "early", "electric", "elegant", "emerald", "epic",
+    "fancy", "fearless", "fierce", "fluffy", "frosty", "funky",
+    "gentle", "giant", "gilded", "glowing", "golden", "grand",
+    "happy", "hardy", "hazy", "hidden", "hollow", "humble",
+    "icy", "idle", "indigo", "inner", "iron", "ivory",
+    "jade", "jazzy", "jolly", "jovial", "jumpy", "just",
+    "keen", "kind", "kinetic", "knotty", "kooky", "kindred",
+    "lazy", "lively", "lofty", "lucky", "lunar", "lush",
+    "magic", "mellow", "mighty", "misty", "modest", "mossy",
+    "nimble", "noble", "nifty", "nocturnal", "northern", "neon",
+    "obsidian", "odd", "olive", "onyx", "opal", "orbital",
+    "patient", "plucky", "polar", "proud", "purple", "quantum",
+    "quick", "quiet", "quirky", "quaint", "quartz", "queenly",
+    "radiant", "rapid", "rusty", "rustic", "royal", "rugged",
+    "sandy", "shiny", "silent", "silver", "snowy", "solar",
+    "tame", "tidy", "tiny", "topaz", "tranquil", "turbo",
+    "ultra", "umber", "unique", "upbeat", "urban", "utopian",
+    "valiant", "velvet", "vivid", "violet", "vintage", "vital",
+    "wandering", "warm", "wild", "windy", "wise", "witty",
+    "xenial", "xeric",
+    "yellow", "young", "yonder", "youthful",
+    "zany", "zealous", "zen", "zesty", "zippy", "zonal",
+];
+
+pub static NOUNS: &[&str] = &[
+    "albatross", "anchor", "antelope", "apollo", "atlas", "aurora",
+    "badger", "beacon", "bison", "breeze", "bobcat", "buffalo",
+    "canyon", "cobra", "comet", "condor", "coyote", "crane",
+    "dingo", "dolphin", "dragon", "dune", "dynamo", "delta",
+    "eagle", "echo", "eclipse", "ember", "emu", "engine",
+    "fern", "ferret", "finch", "fjord", "flamingo", "fox",
+    "galaxy", "gazelle", "gecko", "glacier", "gopher", "griffin",
+    "harbor", "hawk", "hedgehog", "heron", "horizon", "hornet",
+    "ibex", "ibis", "iceberg", "iguana", "impala", "island",
+    "jackal", "jaguar", "jasper", "jellyfish", "jetstream", "jupiter",
+    "kestrel", "kite", "koala", "kraken", "krill", "kudu",
+    "lagoon", "lantern", "lemur", "leopard", "lynx", "lotus",
+    "magpie", "mammoth", "meadow", "meteor", "mongoose", "moose",
+    "narwhal", "nebula", "newt", "nightingale", "nomad", "nugget",
+    "ocelot", "octopus", "orbit", "orca", "osprey", "otter",
+    "panda", "panther", "pelican", "penguin", "phoenix", "puffin",
+    "quail", "quasar", "quartzite", "quetzal", "quokka", "quill",
+    "raccoon", "raven", "reef", "rhino", "river", "rocket",
+    "salmon", "sparrow", "sphinx", "squid", "summit", "swift",
+    "tapir", "thunder", "tiger", "toucan", "tundra", "turtle",
+    "umbra", "unicorn", "universe", "urchin", "uplink", "upsilon",
+    "valley", "vector", "viper", "volcano", "vortex", "vulture",
+    "walrus", "weasel", "whale", "willow", "wolf", "wombat",
+    "xenon", "xylophone",
+    "yak", "yeti", "yarrow", "yucca",
+    "zebra", "zenith", "zephyr", "zeppelin", "zinnia", "zodiac",
+];
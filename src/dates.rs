@@ -0,0 +1,219 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Weekday};
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{prompt, prompt_count, State};
+
+const MAX_COUNT: usize = 16;
+// rejection sampling for weekday/business-hour filters, plenty unless the range is tiny
+const MAX_ATTEMPTS: u32 = 100_000;
+const BUSINESS_HOURS: std::ops::Range<u32> = 9..17;
+
+#[derive(Clone, Copy)]
+pub enum OutputFormat {
+    Iso,
+    Rfc2822,
+    Unix,
+}
+
+pub struct Options {
+    pub weekdays_only: bool,
+    pub business_hours: bool,
+    pub offset: FixedOffset,
+    pub format: OutputFormat,
+}
+
+// "+05:30", "-08:00", "Z" or "UTC"
+fn parse_offset(input: &str) -> Result<FixedOffset, String> {
+    if matches!(input, "Z" | "z" | "UTC" | "utc") {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+    }
+    let (sign, rest) = match input.split_at_checked(1) {
+        Some(("+", rest)) => (1, rest),
+        Some(("-", rest)) => (-1, rest),
+        _ => return Err(format!("offset \"{}\" must start with + or -", input)),
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours = hours.parse::<i32>().map_err(|e| format!("offset hours: {}", e))?;
+    let minutes = minutes.parse::<i32>().map_err(|e| format!("offset minutes: {}", e))?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(|| format!("offset \"{}\" is out of range", input))
+}
+
+impl Options {
+    // space separated: weekdays, business, tz=+02:00, format=iso|rfc2822|unix
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut options = Options {
+            weekdays_only: false,
+            business_hours: false,
+            offset: FixedOffset::east_opt(0).expect("zero offset is valid"),
+            format: OutputFormat::Iso,
+        };
+        for option in input.split_whitespace() {
+            match option.split_once('=') {
+                None if option == "weekdays" => options.weekdays_only = true,
+                None if option == "business" => {
+                    options.weekdays_only = true;
+                    options.business_hours = true;
+                }
+                Some(("tz", offset)) => options.offset = parse_offset(offset)?,
+                Some(("format", "iso")) => options.format = OutputFormat::Iso,
+                Some(("format", "rfc2822")) => options.format = OutputFormat::Rfc2822,
+                Some(("format", "unix")) => options.format = OutputFormat::Unix,
+                _ => return Err(format!("unknown option \"{}\"", option)),
+            }
+        }
+        Ok(options)
+    }
+
+    fn accepts(&self, time: &DateTime<FixedOffset>) -> bool {
+        if self.weekdays_only && matches!(time.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        !self.business_hours || BUSINESS_HOURS.contains(&time.hour())
+    }
+
+    fn format(&self, time: &DateTime<FixedOffset>) -> String {
+        match self.format {
+            OutputFormat::Iso => time.to_rfc3339(),
+            OutputFormat::Rfc2822 => time.to_rfc2822(),
+            OutputFormat::Unix => time.timestamp().to_string(),
+        }
+    }
+}
+
+// ISO-8601 with or without an offset; naive values are read in the chosen offset and a bare
+// date means the start of that day, or the end of it when `end_of_day` is set
+pub fn parse_datetime(input: &str, offset: FixedOffset, end_of_day: bool) -> Result<DateTime<FixedOffset>, String> {
+    let input = input.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time);
+    }
+    let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d").map(|date| {
+                let time = if end_of_day { (23, 59, 59) } else { (0, 0, 0) };
+                date.and_hms_opt(time.0, time.1, time.2).expect("valid time of day")
+            })
+        })
+        .map_err(|_| format!("\"{}\" is not an ISO-8601 date or timestamp", input))?;
+    offset.from_local_datetime(&naive).single().ok_or_else(|| format!("\"{}\" is ambiguous in this offset", input))
+}
+
+pub fn random_datetime(
+    rng: &mut ChaCha20Rng,
+    start: &DateTime<FixedOffset>,
+    end: &DateTime<FixedOffset>,
+    options: &Options,
+) -> Result<DateTime<FixedOffset>, String> {
+    let (start, end) = (start.timestamp(), end.timestamp());
+    if start > end {
+        return Err(String::from("start is after the end"));
+    }
+    // uniform over seconds, filters are rejection sampled so what's left stays uniform
+    for _ in 0..MAX_ATTEMPTS {
+        let seconds = rng.random_range(start..=end);
+        let time = options.offset.timestamp_opt(seconds, 0).single().ok_or("timestamp out of range")?;
+        if options.accepts(&time) {
+            return Ok(time);
+        }
+    }
+    Err(String::from("no time in the range matches the filters"))
+}
+
+// "1d12h", "90m", "45s" or a bare number of seconds
+pub fn parse_duration(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    if let Ok(seconds) = input.parse::<u64>() {
+        return Ok(seconds);
+    }
+    let mut total: u64 = 0;
+    let mut number = String::new();
+    for character in input.chars() {
+        if character.is_ascii_digit() {
+            number.push(character);
+            continue;
+        }
+        let unit = match character {
+            'w' => 604_800,
+            'd' => 86_400,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("unknown duration unit '{}' in \"{}\"", character, input)),
+        };
+        let value = number.parse::<u64>().map_err(|_| format!("missing number before '{}' in \"{}\"", character, input))?;
+        total = value.checked_mul(unit).and_then(|part| total.checked_add(part)).ok_or("duration is too long")?;
+        number.clear();
+    }
+    if !number.is_empty() || input.is_empty() {
+        return Err(format!("\"{}\" needs a unit, e.g. 15m or 2h30m", input));
+    }
+    Ok(total)
+}
+
+// ISO-8601 duration (P1DT2H3M4S) and a compact human form
+pub fn format_duration(seconds: u64) -> (String, String) {
+    let (days, hours, minutes, secs) = (seconds / 86_400, seconds % 86_400 / 3600, seconds % 3600 / 60, seconds % 60);
+    let mut iso = String::from("P");
+    let mut human = Vec::new();
+    if days > 0 {
+        iso.push_str(&format!("{}D", days));
+        human.push(format!("{}d", days));
+    }
+    if hours > 0 || minutes > 0 || secs > 0 || seconds == 0 {
+        iso.push('T');
+    }
+    for (value, unit) in [(hours, 'H'), (minutes, 'M'), (secs, 'S')] {
+        if value > 0 {
+            iso.push_str(&format!("{}{}", value, unit));
+            human.push(format!("{}{}", value, unit.to_ascii_lowercase()));
+        }
+    }
+    if seconds == 0 {
+        iso.push_str("0S");
+        human.push(String::from("0s"));
+    }
+    (iso, human.join(" "))
+}
+
+fn generate_times(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let mode = prompt(terminal, state, "Mode: datetime | date | duration (blank for datetime)")?.trim().to_lowercase();
+
+    if mode == "duration" {
+        let min = parse_duration(&prompt(terminal, state, "Shortest duration (e.g. 15m)")?)?;
+        let max = parse_duration(&prompt(terminal, state, "Longest duration (e.g. 2h30m)")?)?;
+        if min > max {
+            return Err(String::from("shortest duration is longer than the longest"));
+        }
+        let count = prompt_count(terminal, state, MAX_COUNT)?;
+        for _ in 0..count {
+            let (iso, human) = format_duration(rng.random_range(min..=max));
+            state.push_message_output(format!("{} ({})", iso, human));
+        }
+        return Ok(());
+    }
+    if !matches!(mode.as_str(), "" | "datetime" | "date") {
+        return Err(format!("unknown mode \"{}\"", mode));
+    }
+
+    let options = Options::parse(&prompt(terminal, state, "Options: weekdays business tz=+02:00 format=iso|rfc2822|unix")?)?;
+    let start = parse_datetime(&prompt(terminal, state, "Start (ISO-8601, e.g. 2024-01-01)")?, options.offset, false)?;
+    let end = parse_datetime(&prompt(terminal, state, "End (ISO-8601, e.g. 2024-12-31T18:00)")?, options.offset, true)?;
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for _ in 0..count {
+        let time = random_datetime(rng, &start, &end, &options)?;
+        let value = if mode == "date" { time.format("%Y-%m-%d (%a)").to_string() } else { options.format(&time) };
+        state.push_message_output(value);
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_times(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
@@ -0,0 +1,244 @@
+// hand rolled so the binary stays dependency-light, only base32 needs decoding (otp secrets)
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+pub const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+pub const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+// base58 is quadratic in the input length, anything past this takes noticeably long
+pub const MAX_BASE58_BYTES: usize = 4096;
+const Z85_ALPHABET: &[u8; 85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+#[derive(Clone, Copy)]
+pub enum Encoding {
+    Hex,
+    Base64,
+    Base64Url,
+    Base32,
+    Crockford,
+    Base58,
+    Z85,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 7] = [
+        Encoding::Hex, Encoding::Base64, Encoding::Base64Url, Encoding::Base32,
+        Encoding::Crockford, Encoding::Base58, Encoding::Z85,
+    ];
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "hex" => Ok(Encoding::Hex),
+            "base64" | "b64" => Ok(Encoding::Base64),
+            "base64url" | "b64url" => Ok(Encoding::Base64Url),
+            "base32" | "b32" => Ok(Encoding::Base32),
+            "crockford" | "base32crockford" => Ok(Encoding::Crockford),
+            "base58" | "b58" => Ok(Encoding::Base58),
+            "z85" => Ok(Encoding::Z85),
+            other => Err(format!("unknown encoding \"{}\"", other)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Hex => "hex",
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Base32 => "base32",
+            Encoding::Crockford => "crockford",
+            Encoding::Base58 => "base58",
+            Encoding::Z85 => "z85",
+        }
+    }
+
+    // checked before any output is opened, so a bad length never leaves a truncated file behind
+    pub fn check_length(self, length: usize) -> Result<(), String> {
+        match self {
+            Encoding::Base58 if length > MAX_BASE58_BYTES => {
+                Err(format!("base58 is limited to {} bytes, it gets slow on longer input", MAX_BASE58_BYTES))
+            }
+            Encoding::Z85 if !length.is_multiple_of(4) => Err(format!("z85 needs a multiple of 4 bytes, got {}", length)),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn encode(self, bytes: &[u8]) -> Result<String, String> {
+        self.check_length(bytes.len())?;
+        match self {
+            Encoding::Hex => Ok(hex(bytes)),
+            Encoding::Base64 => Ok(base64(bytes, BASE64_ALPHABET, true)),
+            Encoding::Base64Url => Ok(base64(bytes, BASE64URL_ALPHABET, false)),
+            Encoding::Base32 => Ok(base32(bytes, BASE32_ALPHABET, true)),
+            Encoding::Crockford => Ok(base32(bytes, CROCKFORD_ALPHABET, false)),
+            Encoding::Base58 => Ok(base58(bytes)),
+            Encoding::Z85 => Ok(z85(bytes)),
+        }
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter()
+        .flat_map(|byte| [HEX_ALPHABET[(byte >> 4) as usize] as char, HEX_ALPHABET[(byte & 0x0f) as usize] as char])
+        .collect()
+}
+
+// RFC 4648 section 4/5
+fn base64(bytes: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let group = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+
+        let chars = chunk.len() + 1; // 1 byte -> 2 chars, 2 -> 3, 3 -> 4
+        for index in 0..4 {
+            if index < chars {
+                output.push(alphabet[((group >> (18 - index * 6)) & 0x3f) as usize] as char);
+            } else if pad {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+// RFC 4648 section 6, also used for crockford which just swaps the alphabet and drops padding
+pub fn base32(bytes: &[u8], alphabet: &[u8; 32], pad: bool) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    for chunk in bytes.chunks(5) {
+        let mut buffer = [0u8; 8];
+        buffer[3..3 + chunk.len()].copy_from_slice(chunk);
+        let group = u64::from_be_bytes(buffer);
+
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for index in 0..8 {
+            if index < chars {
+                output.push(alphabet[((group >> (35 - index * 5)) & 0x1f) as usize] as char);
+            } else if pad {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+// lenient RFC 4648 decoding for pasted secrets: ignores case, spaces, dashes and padding
+pub fn decode_base32(input: &str) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for character in input.chars().filter(|character| !matches!(character, ' ' | '-' | '=')) {
+        let upper = character.to_ascii_uppercase() as u8;
+        let value = BASE32_ALPHABET.iter().position(|&symbol| symbol == upper)
+            .ok_or_else(|| format!("invalid base32 character '{}'", character))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(output)
+}
+
+// bitcoin alphabet, leading zero bytes become leading '1's
+fn base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    // little-endian base58 digits, repeatedly multiplied in byte by byte
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut output = "1".repeat(zeros);
+    output.extend(digits.iter().rev().map(|&digit| BASE58_ALPHABET[digit as usize] as char));
+    output
+}
+
+// ZeroMQ RFC 32, only defined for inputs that are a multiple of 4 bytes
+fn z85(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() / 4 * 5);
+    for chunk in bytes.chunks(4) {
+        let mut value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let mut block = [0u8; 5];
+        for slot in block.iter_mut().rev() {
+            *slot = Z85_ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+        output.extend(block.iter().map(|&byte| byte as char));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap()).collect()
+    }
+
+    // RFC 4648 section 10
+    const RFC_INPUTS: [&str; 7] = ["", "f", "fo", "foo", "foob", "fooba", "foobar"];
+
+    #[test]
+    fn base32_matches_rfc_4648() {
+        let expected = ["", "MY======", "MZXQ====", "MZXW6===", "MZXW6YQ=", "MZXW6YTB", "MZXW6YTBOI======"];
+        for (input, output) in RFC_INPUTS.iter().zip(expected) {
+            assert_eq!(Encoding::Base32.encode(input.as_bytes()).unwrap(), output);
+            assert_eq!(decode_base32(output).unwrap(), input.as_bytes());
+        }
+        assert_eq!(decode_base32("mzxw 6ytb-oi").unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn base64_and_hex_match_rfc_4648() {
+        let expected = ["", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy"];
+        for (input, output) in RFC_INPUTS.iter().zip(expected) {
+            assert_eq!(Encoding::Base64.encode(input.as_bytes()).unwrap(), output);
+        }
+        assert_eq!(Encoding::Hex.encode(b"foobar").unwrap(), "666f6f626172");
+        assert_eq!(Encoding::Base64Url.encode(&[0xfb, 0xff]).unwrap(), "-_8");
+    }
+
+    // from bitcoin core's base58_encode_decode.json
+    #[test]
+    fn base58_matches_bitcoin_vectors() {
+        let vectors = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            ("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+            ("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+            ("572e4794", "3EFU7m"),
+            ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+            ("10c8511e", "Rt5zm"),
+            ("00000000000000000000", "1111111111"),
+        ];
+        for (input, output) in vectors {
+            assert_eq!(Encoding::Base58.encode(&unhex(input)).unwrap(), output, "input {}", input);
+        }
+        assert!(Encoding::Base58.encode(&vec![1; MAX_BASE58_BYTES + 1]).is_err());
+    }
+
+    // ZeroMQ RFC 32 test vector
+    #[test]
+    fn z85_matches_spec() {
+        assert_eq!(Encoding::Z85.encode(&unhex("864fd26fb559f75b")).unwrap(), "HelloWorld");
+        assert!(Encoding::Z85.encode(b"abc").is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use std::io::Write;
+
+use chrono::{Days, NaiveDate};
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+use serde::Deserialize;
+
+use crate::{cli, fake, ids, prompt, regex_gen, template, words, State};
+
+// regex columns with * or + get this cap so they stay bounded
+const REGEX_REPEAT_CAP: u32 = 16;
+const SQL_BATCH_ROWS: u64 = 500;
+
+#[derive(Deserialize)]
+pub struct Schema {
+    pub table: Option<String>,
+    pub columns: Vec<ColumnSpec>,
+}
+
+#[derive(Deserialize)]
+pub struct ColumnSpec {
+    pub name: String,
+    pub generator: String,
+    // chance of the value being NULL/empty instead
+    #[serde(default)]
+    pub nulls: f64,
+}
+
+pub enum Generator {
+    Sequence(i64),
+    Range { min: i64, max: i64 },
+    Float { min: f64, max: f64, decimals: usize },
+    Bool,
+    Word,
+    UuidV4,
+    UuidV7,
+    Date { start: NaiveDate, days: u64 },
+    Choice(Vec<String>),
+    Template(Vec<template::Token>),
+    Regex(regex_gen::Pattern),
+    Fake(fake::FakeKind),
+}
+
+pub enum Value {
+    Null,
+    Int(i64),
+    Float(f64, usize),
+    Bool(bool),
+    Text(String),
+}
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Csv,
+    JsonLines,
+    Sql,
+}
+
+pub struct Column {
+    name: String,
+    generator: Generator,
+    nulls: f64,
+}
+
+fn parse_pair<T: std::str::FromStr>(arguments: &str, what: &str) -> Result<(T, T), String>
+where
+    T::Err: std::fmt::Display,
+{
+    let parts: Vec<&str> = arguments.split_whitespace().collect();
+    if parts.len() < 2 {
+        return Err(format!("{} needs a minimum and a maximum", what));
+    }
+    let min = parts[0].parse::<T>().map_err(|e| format!("{} minimum: {}", what, e))?;
+    let max = parts[1].parse::<T>().map_err(|e| format!("{} maximum: {}", what, e))?;
+    Ok((min, max))
+}
+
+impl Generator {
+    // "range 18 90", "choice free|pro|team", "template {upper:3}-{digit:4}", "email", ...
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (name, arguments) = spec.split_once(' ').unwrap_or((spec, ""));
+        let generator = match name {
+            "sequence" => Generator::Sequence(match arguments.trim() {
+                "" => 1,
+                start => start.parse::<i64>().map_err(|e| format!("sequence start: {}", e))?,
+            }),
+            "range" | "int" => {
+                let (min, max) = parse_pair::<i64>(arguments, "range")?;
+                if min > max {
+                    return Err(String::from("range minimum is above the maximum"));
+                }
+                Generator::Range { min, max }
+            }
+            "float" => {
+                let (min, max) = parse_pair::<f64>(arguments, "float")?;
+                if !min.is_finite() || !max.is_finite() || min >= max {
+                    return Err(String::from("float minimum must be below the maximum"));
+                }
+                let decimals = match arguments.split_whitespace().nth(2) {
+                    Some(decimals) => decimals.parse::<usize>().map_err(|e| format!("float decimals: {}", e))?,
+                    None => 2,
+                };
+                Generator::Float { min, max, decimals }
+            }
+            "bool" => Generator::Bool,
+            "word" => Generator::Word,
+            "uuid" | "uuid4" => Generator::UuidV4,
+            "uuid7" => Generator::UuidV7,
+            "date" => {
+                let (start, end) = parse_pair::<NaiveDate>(arguments, "date")?;
+                if start > end {
+                    return Err(String::from("date start is after the end"));
+                }
+                Generator::Date { start, days: (end - start).num_days() as u64 }
+            }
+            "choice" => {
+                let options: Vec<String> = arguments.split('|').map(|option| option.trim().to_string()).collect();
+                if options.iter().all(String::is_empty) {
+                    return Err(String::from("choice needs options like a|b|c"));
+                }
+                Generator::Choice(options)
+            }
+            "template" => Generator::Template(template::parse(arguments)?),
+            "regex" => Generator::Regex(regex_gen::Pattern::parse(arguments, Some(REGEX_REPEAT_CAP))?),
+            // FakeKind treats blank as "name", a column with no generator is more likely a schema mistake
+            "" => return Err(String::from("generator is empty, e.g. \"email\" or \"range 1 100\"")),
+            _ => Generator::Fake(fake::FakeKind::parse(spec)?),
+        };
+        Ok(generator)
+    }
+
+    fn generate(&self, rng: &mut ChaCha20Rng, id_generator: &mut ids::IdGenerator, row: u64) -> Result<Value, String> {
+        let value = match self {
+            Generator::Sequence(start) => {
+                let value = i64::try_from(row).ok().and_then(|row| start.checked_add(row));
+                Value::Int(value.ok_or_else(|| format!("sequence from {} overflows at row {}", start, row + 1))?)
+            }
+            Generator::Range { min, max } => Value::Int(rng.random_range(*min..=*max)),
+            Generator::Float { min, max, decimals } => Value::Float(rng.random_range(*min..*max), *decimals),
+            Generator::Bool => Value::Bool(rng.random_bool(0.5)),
+            Generator::Word => Value::Text(words::wordlist[rng.random_range(0..words::wordlist.len())].to_string()),
+            Generator::UuidV4 => Value::Text(ids::uuid_v4(rng)),
+            Generator::UuidV7 => Value::Text(id_generator.generate(&ids::IdKind::UuidV7, rng)?),
+            Generator::Date { start, days } => {
+                let date = *start + Days::new(rng.random_range(0..=*days));
+                Value::Text(date.format("%Y-%m-%d").to_string())
+            }
+            Generator::Choice(options) => Value::Text(options[rng.random_range(0..options.len())].clone()),
+            Generator::Template(tokens) => Value::Text(template::fill(tokens, rng)),
+            Generator::Regex(pattern) => Value::Text(pattern.generate(rng)),
+            Generator::Fake(kind) => Value::Text(kind.generate(rng)),
+        };
+        Ok(value)
+    }
+}
+
+impl Format {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" | "jsonl" | "jsonlines" => Ok(Format::JsonLines),
+            "sql" => Ok(Format::Sql),
+            other => Err(format!("unknown format \"{}\", expected csv, jsonl or sql", other)),
+        }
+    }
+}
+
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Int(number) => number.to_string(),
+        Value::Float(number, decimals) => format!("{:.*}", decimals, number),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Text(text) if text.contains([',', '"', '\n', '\r']) => format!("\"{}\"", text.replace('"', "\"\"")),
+        Value::Text(text) => text.clone(),
+    }
+}
+
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("null"),
+        Value::Text(text) => serde_json::to_string(text).expect("strings always serialize"),
+        other => csv_field(other),
+    }
+}
+
+fn sql_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("NULL"),
+        Value::Bool(true) => String::from("TRUE"),
+        Value::Bool(false) => String::from("FALSE"),
+        Value::Text(text) => format!("'{}'", text.replace('\'', "''")),
+        other => csv_field(other),
+    }
+}
+
+fn sql_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub fn load(path: &str) -> Result<(String, Vec<Column>), String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let schema: Schema = if path.ends_with(".json") {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())?
+    } else {
+        toml::from_str(&contents).map_err(|e| e.to_string())?
+    };
+    if schema.columns.is_empty() {
+        return Err(String::from("schema has no columns"));
+    }
+
+    let mut columns = Vec::with_capacity(schema.columns.len());
+    for spec in schema.columns {
+        if !(0.0..=1.0).contains(&spec.nulls) {
+            return Err(format!("column {}: nulls must be between 0 and 1", spec.name));
+        }
+        let generator = Generator::parse(&spec.generator).map_err(|e| format!("column {}: {}", spec.name, e))?;
+        columns.push(Column { name: spec.name, generator, nulls: spec.nulls });
+    }
+    Ok((schema.table.unwrap_or_else(|| String::from("records")), columns))
+}
+
+// rows are generated and written one at a time, so memory use doesn't grow with the row count
+pub fn write_rows(
+    output: &mut dyn Write,
+    table: &str,
+    columns: &[Column],
+    rows: u64,
+    format: Format,
+    rng: &mut ChaCha20Rng,
+) -> Result<(), String> {
+    let names: Vec<String> = columns.iter().map(|column| column.name.clone()).collect();
+    let io_error = |e: std::io::Error| e.to_string();
+
+    if let Format::Csv = format {
+        let header: Vec<String> = names.iter().map(|name| csv_field(&Value::Text(name.clone()))).collect();
+        writeln!(output, "{}", header.join(",")).map_err(io_error)?;
+    }
+    let sql_columns: Vec<String> = names.iter().map(|name| sql_identifier(name)).collect();
+    // thousands of rows land in the same millisecond, its counter keeps uuid7 columns sorted
+    let mut id_generator = ids::IdGenerator::default();
+
+    for row in 0..rows {
+        let values: Vec<Value> = columns.iter()
+            .map(|column| {
+                let value = column.generator.generate(rng, &mut id_generator, row)?;
+                Ok(if column.nulls > 0.0 && rng.random_bool(column.nulls) { Value::Null } else { value })
+            })
+            .collect::<Result<_, String>>()?;
+
+        match format {
+            Format::Csv => {
+                let fields: Vec<String> = values.iter().map(csv_field).collect();
+                writeln!(output, "{}", fields.join(",")).map_err(io_error)?;
+            }
+            Format::JsonLines => {
+                let fields: Vec<String> = names.iter().zip(&values)
+                    .map(|(name, value)| format!("{}:{}", json_value(&Value::Text(name.clone())), json_value(value)))
+                    .collect();
+                writeln!(output, "{{{}}}", fields.join(",")).map_err(io_error)?;
+            }
+            Format::Sql => {
+                // multi-row inserts in batches, much faster to load than one statement per row
+                if row % SQL_BATCH_ROWS == 0 {
+                    write!(output, "INSERT INTO {} ({}) VALUES\n  ", sql_identifier(table), sql_columns.join(", ")).map_err(io_error)?;
+                } else {
+                    write!(output, ",\n  ").map_err(io_error)?;
+                }
+                let fields: Vec<String> = values.iter().map(sql_value).collect();
+                write!(output, "({})", fields.join(", ")).map_err(io_error)?;
+                if (row + 1) % SQL_BATCH_ROWS == 0 || row + 1 == rows {
+                    writeln!(output, ";").map_err(io_error)?;
+                }
+            }
+        }
+    }
+    output.flush().map_err(io_error)
+}
+
+pub fn export(schema_path: &str, rows: u64, format: Format, output_path: Option<&str>, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let (table, columns) = load(schema_path)?;
+    let mut output = cli::open_output(output_path)?;
+    write_rows(&mut *output, &table, &columns, rows, format, rng)
+}
+
+fn export_records(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let schema_path = prompt(terminal, state, "Schema file (.toml or .json)")?;
+    let (table, columns) = load(schema_path.trim())?;
+
+    let input = prompt(terminal, state, "Rows (blank for 1000)")?;
+    let rows = match input.trim() {
+        "" => 1000,
+        value => value.parse::<u64>().map_err(|e| e.to_string())?,
+    };
+    let format = Format::parse(&prompt(terminal, state, "Format (csv, jsonl, sql)")?)?;
+    let output_path = prompt(terminal, state, "Output file")?;
+    if output_path.trim().is_empty() {
+        return Err(String::from("an output file is needed, the pane is too small for bulk rows"));
+    }
+
+    let seed = prompt(terminal, state, "Seed (blank for random)")?;
+    let mut seeded = (!seed.trim().is_empty()).then(|| fake::seeded_rng(&seed));
+    let rng = seeded.as_mut().unwrap_or(rng);
+
+    let mut output = cli::open_output(Some(output_path.trim()))?;
+    write_rows(&mut *output, &table, &columns, rows, format, rng)?;
+    state.push_message_output(format!("wrote {} rows of {} to {}", rows, table, output_path.trim()));
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = export_records(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid7_rows_are_strictly_increasing() {
+        let columns = [Column { name: String::from("id"), generator: Generator::UuidV7, nulls: 0.0 }];
+        let mut output = Vec::new();
+        write_rows(&mut output, "records", &columns, 10_000, Format::Csv, &mut fake::seeded_rng("uuid7")).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let ids: Vec<&str> = output.lines().skip(1).collect();
+        assert_eq!(ids.len(), 10_000);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
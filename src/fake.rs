@@ -0,0 +1,161 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+use sha2::{Digest, Sha256};
+
+use crate::{fake_corpus::*, prompt, prompt_count, words, State};
+
+const MAX_COUNT: usize = 16;
+const KINDS_HELP: &str = "name, username, email, phone, address, ipv4 [cidr], ipv6 [cidr], mac [global], port, useragent";
+
+pub enum FakeKind {
+    Name,
+    Username,
+    Email,
+    Phone,
+    Address,
+    Ipv4 { network: u32, prefix: u32 },
+    Ipv6 { network: u128, prefix: u32 },
+    Mac { local: bool },
+    Port,
+    UserAgent,
+}
+
+fn pick<'a, T>(rng: &mut ChaCha20Rng, items: &'a [T]) -> &'a T {
+    &items[rng.random_range(0..items.len())]
+}
+
+// "10.0.0.0/8" style, the host bits of the address are ignored and a bare address is a single host
+fn parse_cidr<T: std::str::FromStr>(input: &str, bits: u32) -> Result<(T, u32), String> {
+    let (address, prefix) = match input.split_once('/') {
+        Some((address, prefix)) => (address, prefix.parse::<u32>().map_err(|e| format!("prefix: {}", e))?),
+        None => (input, bits),
+    };
+    let address = address.parse::<T>().map_err(|_| format!("invalid address \"{}\"", address))?;
+    if prefix > bits {
+        return Err(format!("prefix /{} is longer than {} bits", prefix, bits));
+    }
+    Ok((address, prefix))
+}
+
+// a seed turns a ChaCha stream fully reproducible; numbers are used as-is, anything else is hashed
+pub fn seeded_rng(seed: &str) -> ChaCha20Rng {
+    match seed.trim().parse::<u64>() {
+        Ok(number) => ChaCha20Rng::seed_from_u64(number),
+        Err(_) => ChaCha20Rng::from_seed(Sha256::digest(seed.trim().as_bytes()).into()),
+    }
+}
+
+impl FakeKind {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parts = input.split_whitespace();
+        let name = parts.next().unwrap_or("name").to_lowercase();
+        let argument = parts.next();
+        let kind = match name.as_str() {
+            "name" => FakeKind::Name,
+            "username" | "user" => FakeKind::Username,
+            "email" => FakeKind::Email,
+            "phone" => FakeKind::Phone,
+            "address" => FakeKind::Address,
+            "ipv4" | "ip" => {
+                let (address, prefix) = parse_cidr::<Ipv4Addr>(argument.unwrap_or("0.0.0.0/0"), 32)?;
+                let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+                FakeKind::Ipv4 { network: u32::from(address) & mask, prefix }
+            }
+            "ipv6" => {
+                // 2001:db8::/32 is the documentation prefix, a safe default for fixtures
+                let (address, prefix) = parse_cidr::<Ipv6Addr>(argument.unwrap_or("2001:db8::/32"), 128)?;
+                let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+                FakeKind::Ipv6 { network: u128::from(address) & mask, prefix }
+            }
+            "mac" => match argument {
+                None | Some("local") => FakeKind::Mac { local: true },
+                Some("global") => FakeKind::Mac { local: false },
+                Some(other) => return Err(format!("mac takes local or global, got \"{}\"", other)),
+            },
+            "port" => FakeKind::Port,
+            "useragent" | "ua" => FakeKind::UserAgent,
+            other => return Err(format!("unknown fake data type \"{}\", expected one of: {}", other, KINDS_HELP)),
+        };
+        Ok(kind)
+    }
+
+    pub fn generate(&self, rng: &mut ChaCha20Rng) -> String {
+        match self {
+            FakeKind::Name => format!("{} {}", pick(rng, FIRST_NAMES), pick(rng, LAST_NAMES)),
+            FakeKind::Username => username(rng),
+            FakeKind::Email => format!("{}@{}", username(rng), pick(rng, EMAIL_DOMAINS)),
+            FakeKind::Phone => {
+                // 555-0100 to 555-0199 is reserved for fiction in the NANP
+                format!("+1 ({}{:02}) 555-01{:02}", rng.random_range(2..10), rng.random_range(0..100), rng.random_range(0..100))
+            }
+            FakeKind::Address => {
+                let (city, state) = pick(rng, CITIES);
+                format!(
+                    "{} {} {}, {}, {} {:05}",
+                    rng.random_range(1..10000), pick(rng, STREET_NAMES), pick(rng, STREET_SUFFIXES), city, state,
+                    rng.random_range(501..100000)
+                )
+            }
+            FakeKind::Ipv4 { network, prefix } => {
+                let host_bits = 32 - prefix;
+                let host_mask = if host_bits == 32 { u32::MAX } else { (1u32 << host_bits) - 1 };
+                // skip the network and broadcast addresses when the subnet has room for hosts
+                let host = if host_bits >= 2 { rng.random_range(1..host_mask) } else { rng.random::<u32>() & host_mask };
+                Ipv4Addr::from(network | host).to_string()
+            }
+            FakeKind::Ipv6 { network, prefix } => {
+                let host_mask = u128::MAX.checked_shr(*prefix).unwrap_or(0);
+                Ipv6Addr::from(network | (rng.random::<u128>() & host_mask)).to_string()
+            }
+            FakeKind::Mac { local } => {
+                let mut bytes: [u8; 6] = rng.random();
+                bytes[0] &= 0xfe; // unicast
+                if *local {
+                    bytes[0] |= 0x02; // locally administered, can't clash with a vendor OUI
+                } else {
+                    bytes[0] &= 0xfd;
+                }
+                bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(":")
+            }
+            FakeKind::Port => rng.random_range(1024..=65535u16).to_string(),
+            FakeKind::UserAgent => pick(rng, USER_AGENTS).to_string(),
+        }
+    }
+}
+
+fn username(rng: &mut ChaCha20Rng) -> String {
+    let first = pick(rng, FIRST_NAMES).to_lowercase();
+    let last = pick(rng, LAST_NAMES).to_lowercase();
+    match rng.random_range(0..4) {
+        0 => format!("{}.{}", first, last),
+        1 => format!("{}{}", &first[..1], last),
+        2 => format!("{}_{}{}", first, last, rng.random_range(1..100)),
+        _ => format!("{}{}", pick(rng, words::wordlist), rng.random_range(10..1000)),
+    }
+}
+
+fn generate_fake_data(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let input = prompt(terminal, state, &format!("Type ({})", KINDS_HELP))?;
+    let kind = FakeKind::parse(&input)?;
+
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    let seed = prompt(terminal, state, "Seed (blank for random)")?;
+    let mut seeded = (!seed.trim().is_empty()).then(|| seeded_rng(&seed));
+    let rng = seeded.as_mut().unwrap_or(rng);
+
+    for _ in 0..count {
+        let value = kind.generate(rng);
+        state.push_message_output(value);
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_fake_data(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
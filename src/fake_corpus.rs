@@ -0,0 +1,49 @@
+// embedded like words.rs so the fake data generator works from the single binary
+pub static FIRST_NAMES: &[&str] = &[
+    "Aaliyah", "Adam", "Aiden", "Alice", "Amara", "Amelia", "Andre", "Anna", "Arjun", "Ava", "Benjamin", "Carlos",
+    "Chen", "Chloe", "Daniel", "David", "Diego", "Elena", "Elijah", "Emily", "Emma", "Ethan", "Fatima", "Gabriel",
+    "Grace", "Hana", "Hannah", "Hugo", "Isabella", "Ivan", "Jack", "James", "Jasmine", "Jin", "Julia", "Kai",
+    "Kenji", "Layla", "Leo", "Liam", "Lucas", "Lucia", "Maya", "Mei", "Mia", "Mohammed", "Nadia", "Noah",
+    "Nora", "Oliver", "Olivia", "Omar", "Priya", "Rafael", "Ravi", "Sara", "Sofia", "Tomas", "Yara", "Zoe",
+];
+
+pub static LAST_NAMES: &[&str] = &[
+    "Adams", "Ahmed", "Alvarez", "Anderson", "Bauer", "Brown", "Chen", "Clark", "Costa", "Davis", "Dubois", "Evans",
+    "Fischer", "Garcia", "Gonzalez", "Gupta", "Hall", "Hansen", "Harris", "Hernandez", "Ito", "Jackson", "Jensen",
+    "Johnson", "Kim", "Kowalski", "Kumar", "Lee", "Lopez", "Martin", "Martinez", "Meyer", "Miller", "Moore",
+    "Muller", "Nakamura", "Nguyen", "Novak", "Okafor", "Olsen", "Patel", "Perez", "Rossi", "Santos", "Schmidt",
+    "Silva", "Singh", "Smith", "Suzuki", "Taylor", "Thomas", "Tanaka", "Walker", "Wang", "White", "Williams",
+    "Wilson", "Wright", "Young", "Zhang",
+];
+
+pub static STREET_NAMES: &[&str] = &[
+    "Maple", "Oak", "Cedar", "Pine", "Elm", "Willow", "Birch", "Chestnut", "Walnut", "Spruce", "Highland", "Lakeview",
+    "Hillcrest", "Riverside", "Sunset", "Meadow", "Park", "Church", "Mill", "Station", "Market", "King", "Queen",
+    "Victoria", "Washington", "Lincoln", "Jefferson", "Franklin", "Harbor", "Orchard",
+];
+
+pub static STREET_SUFFIXES: &[&str] = &["St", "Ave", "Rd", "Blvd", "Ln", "Dr", "Ct", "Way", "Pl", "Terrace"];
+
+// (city, state) pairs so the two always agree
+pub static CITIES: &[(&str, &str)] = &[
+    ("Springfield", "IL"), ("Portland", "OR"), ("Austin", "TX"), ("Denver", "CO"), ("Madison", "WI"),
+    ("Raleigh", "NC"), ("Boise", "ID"), ("Burlington", "VT"), ("Tucson", "AZ"), ("Savannah", "GA"),
+    ("Omaha", "NE"), ("Richmond", "VA"), ("Albany", "NY"), ("Columbus", "OH"), ("Salem", "MA"),
+    ("Fresno", "CA"), ("Tacoma", "WA"), ("Reno", "NV"), ("Tulsa", "OK"), ("Anchorage", "AK"),
+];
+
+// RFC 2606 reserved names only, so generated emails can never reach a real inbox
+pub static EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net", "mail.example", "corp.test"];
+
+pub static USER_AGENTS: &[&str] = &[
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_5) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Safari/605.1.15",
+    "Mozilla/5.0 (X11; Linux x86_64; rv:127.0) Gecko/20100101 Firefox/127.0",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:127.0) Gecko/20100101 Firefox/127.0",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Mobile Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36 Edg/126.0.0.0",
+    "curl/8.8.0",
+    "python-requests/2.32.3",
+    "Go-http-client/2.0",
+];
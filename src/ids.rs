@@ -0,0 +1,171 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{encoding::CROCKFORD_ALPHABET, parse_or_default, prompt, prompt_count, State};
+
+const MAX_COUNT: usize = 100;
+const NANOID_ALPHABET: &str = "_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NANOID_LENGTH: usize = 21;
+// twitter's snowflake epoch (2010-11-04), keeps the 41 bit timestamp valid until 2080
+const SNOWFLAKE_EPOCH_MILLIS: u64 = 1_288_834_974_657;
+
+pub enum IdKind {
+    UuidV4,
+    UuidV7,
+    Ulid,
+    NanoId { alphabet: Vec<char>, length: usize },
+    Snowflake { machine_id: u16 },
+}
+
+// keeps the per-millisecond counters so bulk v7/ULID/snowflake ids stay strictly ordered
+#[derive(Default)]
+pub struct IdGenerator {
+    uuid_v7_millis: u64,
+    uuid_v7_counter: u16,
+    ulid_millis: u64,
+    ulid_random: u128,
+    snowflake_millis: u64,
+    snowflake_sequence: u16,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_millis() as u64).unwrap_or(0)
+}
+
+fn format_uuid(bytes: [u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+pub fn uuid_v4(rng: &mut ChaCha20Rng) -> String {
+    let mut bytes: [u8; 16] = rng.random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+    format_uuid(bytes)
+}
+
+// 48 bit big-endian unix millis followed by random bits (RFC 9562)
+fn uuid_v7_bytes(rng: &mut ChaCha20Rng, millis: u64) -> [u8; 16] {
+    let mut bytes: [u8; 16] = rng.random();
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x70; // version 7
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+// 26 crockford base32 chars: 48 bit timestamp + 80 random bits
+fn format_ulid(millis: u64, random: u128) -> String {
+    let value = ((millis as u128) << 80) | (random & ((1 << 80) - 1));
+    (0..26).rev()
+        .map(|index| CROCKFORD_ALPHABET[((value >> (index * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+pub fn nanoid(rng: &mut ChaCha20Rng, alphabet: &[char], length: usize) -> String {
+    (0..length).map(|_| alphabet[rng.random_range(0..alphabet.len())]).collect()
+}
+
+impl IdGenerator {
+    pub fn generate(&mut self, kind: &IdKind, rng: &mut ChaCha20Rng) -> Result<String, String> {
+        match kind {
+            IdKind::UuidV4 => Ok(uuid_v4(rng)),
+            IdKind::UuidV7 => {
+                let mut millis = now_millis().max(self.uuid_v7_millis);
+                // RFC 9562 section 6.2 method 1: the 12 bit rand_a field counts up within a millisecond,
+                // starting from a random value with the top bit clear so there's room to count. when it
+                // runs out the timestamp borrows the next millisecond, as the RFC allows
+                if millis == self.uuid_v7_millis && self.uuid_v7_counter < 0xfff {
+                    self.uuid_v7_counter += 1;
+                } else {
+                    if millis == self.uuid_v7_millis {
+                        millis += 1;
+                    }
+                    self.uuid_v7_counter = rng.random::<u16>() & 0x7ff;
+                }
+                self.uuid_v7_millis = millis;
+                let mut bytes = uuid_v7_bytes(rng, millis);
+                bytes[6] = 0x70 | (self.uuid_v7_counter >> 8) as u8;
+                bytes[7] = self.uuid_v7_counter as u8;
+                Ok(format_uuid(bytes))
+            }
+            IdKind::NanoId { alphabet, length } => Ok(nanoid(rng, alphabet, *length)),
+            IdKind::Ulid => {
+                // never go backwards if the clock does
+                let millis = now_millis().max(self.ulid_millis);
+                // monotonic ULIDs: same millisecond means increment the previous random part
+                if millis == self.ulid_millis {
+                    self.ulid_random += 1;
+                    if self.ulid_random >> 80 != 0 {
+                        return Err(String::from("ULID random part overflowed within one millisecond"));
+                    }
+                } else {
+                    self.ulid_random = rng.random::<u128>() & ((1 << 80) - 1);
+                }
+                self.ulid_millis = millis;
+                Ok(format_ulid(millis, self.ulid_random))
+            }
+            IdKind::Snowflake { machine_id } => {
+                let millis = now_millis().max(self.snowflake_millis);
+                if millis == self.snowflake_millis {
+                    self.snowflake_sequence += 1;
+                    if self.snowflake_sequence > 0xfff {
+                        return Err(String::from("more than 4096 snowflake ids in one millisecond"));
+                    }
+                } else {
+                    self.snowflake_sequence = 0;
+                }
+                self.snowflake_millis = millis;
+                let timestamp = millis.saturating_sub(SNOWFLAKE_EPOCH_MILLIS) & ((1 << 41) - 1);
+                Ok(((timestamp << 22) | ((*machine_id as u64) << 12) | self.snowflake_sequence as u64).to_string())
+            }
+        }
+    }
+}
+
+fn prompt_kind(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<IdKind, String> {
+    let input = prompt(terminal, state, "ID type (uuid4, uuid7, ulid, nanoid, snowflake)")?;
+    match input.trim().to_lowercase().as_str() {
+        "" | "uuid" | "uuid4" | "v4" => Ok(IdKind::UuidV4),
+        "uuid7" | "v7" => Ok(IdKind::UuidV7),
+        "ulid" => Ok(IdKind::Ulid),
+        "nanoid" => {
+            let alphabet = prompt(terminal, state, "NanoID alphabet (blank for A-Za-z0-9_-)")?;
+            let alphabet: Vec<char> = if alphabet.is_empty() { NANOID_ALPHABET.chars().collect() } else { alphabet.chars().collect() };
+            let mut unique = alphabet.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            if unique.len() != alphabet.len() || alphabet.len() < 2 {
+                return Err(String::from("alphabet needs at least 2 characters and no duplicates"));
+            }
+            let length = parse_or_default(&prompt(terminal, state, "NanoID length (blank for 21)")?, NANOID_LENGTH, 1..=256, "length")?;
+            Ok(IdKind::NanoId { alphabet, length })
+        }
+        "snowflake" => {
+            let random_machine = rng.random_range(0..1024);
+            // 10 bits in the snowflake layout
+            let machine_id = parse_or_default(&prompt(terminal, state, "Machine id 0-1023 (blank for random)")?, random_machine, 0..=1023, "machine id")?;
+            Ok(IdKind::Snowflake { machine_id })
+        }
+        other => Err(format!("unknown id type \"{}\"", other)),
+    }
+}
+
+fn generate_ids(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let kind = prompt_kind(terminal, state, rng)?;
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for _ in 0..count {
+        let id = state.id_generator.generate(&kind, rng)?;
+        state.push_message_output(id);
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_ids(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
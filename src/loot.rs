@@ -0,0 +1,363 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rand::distr::{weighted::WeightedIndex, Distribution};
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+use serde::Deserialize;
+
+use crate::{parse_or_default, prompt, State};
+
+const MAX_PULLS: u64 = 1_000_000;
+const MAX_TABLE_DEPTH: usize = 16;
+
+#[derive(Deserialize)]
+pub struct LootTable {
+    pub name: Option<String>,
+    pub cost_per_pull: Option<f64>,
+    pub entries: Vec<Entry>,
+    #[serde(default)]
+    pub guaranteed: Vec<Guaranteed>,
+    #[serde(default)]
+    pub pity: Vec<PityRule>,
+    #[serde(default)]
+    pub tables: HashMap<String, Vec<Entry>>,
+}
+
+// an entry either drops an item directly or rolls again on a nested table
+#[derive(Deserialize)]
+pub struct Entry {
+    pub item: Option<String>,
+    pub table: Option<String>,
+    pub weight: f64,
+    pub rarity: Option<String>,
+}
+
+// item handed out on every Nth pull regardless of the roll
+#[derive(Deserialize)]
+pub struct Guaranteed {
+    pub item: String,
+    #[serde(default = "default_every")]
+    pub every: u64,
+}
+
+fn default_every() -> u64 {
+    1
+}
+
+// after `after` pulls in a row without this rarity, the next pull is forced to it
+#[derive(Deserialize)]
+pub struct PityRule {
+    pub rarity: String,
+    pub after: u32,
+}
+
+pub struct Reward {
+    pub item: String,
+    pub rarity: Option<String>,
+}
+
+pub struct Pull {
+    pub drops: Vec<Reward>,
+    pub pity_triggered: bool,
+}
+
+// a loaded table plus the pity counters, kept in State so single pulls carry pity over
+pub struct Session {
+    pub path: String,
+    pub table: LootTable,
+    pub pulls_done: u64,
+    pity_counters: Vec<u32>,
+}
+
+pub fn load(path: &str) -> Result<LootTable, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let table: LootTable = if path.ends_with(".json") {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())?
+    } else {
+        toml::from_str(&contents).map_err(|e| e.to_string())?
+    };
+    table.validate()?;
+    Ok(table)
+}
+
+impl LootTable {
+    fn validate(&self) -> Result<(), String> {
+        self.validate_entries("entries", &self.entries, 0)?;
+        for guaranteed in &self.guaranteed {
+            if guaranteed.every == 0 {
+                return Err(format!("guaranteed item \"{}\" needs every >= 1", guaranteed.item));
+            }
+        }
+        for rule in &self.pity {
+            if rule.after == 0 {
+                return Err(format!("pity for \"{}\" needs after >= 1", rule.rarity));
+            }
+            if !self.entries.iter().any(|entry| entry.rarity.as_deref() == Some(rule.rarity.as_str())) {
+                return Err(format!("pity rarity \"{}\" is not used by any top-level entry", rule.rarity));
+            }
+            // a forced pull has to land on the pity rarity or the counter never resets
+            for entry in self.entries.iter().filter(|entry| entry.rarity.as_ref() == Some(&rule.rarity)) {
+                if let Some(other) = self.overriding_rarity(entry, &rule.rarity) {
+                    return Err(format!("a \"{}\" entry can drop \"{}\" through a nested table, pity would never reset", rule.rarity, other));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_entries(&self, name: &str, entries: &[Entry], depth: usize) -> Result<(), String> {
+        if depth > MAX_TABLE_DEPTH {
+            return Err(format!("tables nested deeper than {} (is \"{}\" recursive?)", MAX_TABLE_DEPTH, name));
+        }
+        if entries.is_empty() {
+            return Err(format!("table \"{}\" has no entries", name));
+        }
+        for entry in entries {
+            if !entry.weight.is_finite() || entry.weight <= 0.0 {
+                return Err(format!("table \"{}\" has an entry with invalid weight {}", name, entry.weight));
+            }
+            match (&entry.item, &entry.table) {
+                (Some(_), None) => {}
+                (None, Some(nested)) => {
+                    let nested_entries = self.tables.get(nested)
+                        .ok_or_else(|| format!("table \"{}\" references unknown table \"{}\"", name, nested))?;
+                    self.validate_entries(nested, nested_entries, depth + 1)?;
+                }
+                _ => return Err(format!("table \"{}\" has an entry that needs exactly one of item or table", name)),
+            }
+        }
+        Ok(())
+    }
+
+    // first rarity other than `rarity` that a roll starting at `entry` can end on. only called
+    // after validate_entries, so nested tables exist and aren't recursive
+    fn overriding_rarity<'a>(&'a self, entry: &'a Entry, rarity: &str) -> Option<&'a str> {
+        if let Some(own) = entry.rarity.as_deref().filter(|own| *own != rarity) {
+            return Some(own);
+        }
+        let nested = self.tables.get(entry.table.as_ref()?)?;
+        nested.iter().find_map(|nested| self.overriding_rarity(nested, rarity))
+    }
+
+    // per-pull chance of every item from the weights alone, ignoring pity
+    pub fn item_probabilities(&self) -> BTreeMap<String, f64> {
+        let mut probabilities = BTreeMap::new();
+        self.accumulate_probabilities(&self.entries, 1.0, &mut probabilities);
+        for guaranteed in &self.guaranteed {
+            *probabilities.entry(guaranteed.item.clone()).or_insert(0.0) += 1.0 / guaranteed.every as f64;
+        }
+        probabilities
+    }
+
+    fn accumulate_probabilities(&self, entries: &[Entry], scale: f64, probabilities: &mut BTreeMap<String, f64>) {
+        let total: f64 = entries.iter().map(|entry| entry.weight).sum();
+        for entry in entries {
+            let chance = scale * entry.weight / total;
+            if let Some(item) = &entry.item {
+                *probabilities.entry(item.clone()).or_insert(0.0) += chance;
+            } else if let Some(nested) = entry.table.as_ref().and_then(|name| self.tables.get(name)) {
+                self.accumulate_probabilities(nested, chance, probabilities);
+            }
+        }
+    }
+
+    // only called on validated tables, so weights are positive and tables exist
+    fn roll(&self, entries: &[&Entry], inherited_rarity: Option<&String>, rng: &mut ChaCha20Rng) -> Reward {
+        let distribution = WeightedIndex::new(entries.iter().map(|entry| entry.weight))
+            .expect("validated table has positive weights");
+        let entry = entries[distribution.sample(rng)];
+        let rarity = entry.rarity.as_ref().or(inherited_rarity);
+
+        match (&entry.item, &entry.table) {
+            (Some(item), _) => Reward { item: item.clone(), rarity: rarity.cloned() },
+            (None, Some(nested)) => {
+                let nested: Vec<&Entry> = self.tables[nested].iter().collect();
+                self.roll(&nested, rarity, rng)
+            }
+            (None, None) => unreachable!("validated entry has an item or a table"),
+        }
+    }
+}
+
+impl Session {
+    pub fn new(path: String, table: LootTable) -> Self {
+        let pity_counters = vec![0; table.pity.len()];
+        Self { path, table, pulls_done: 0, pity_counters }
+    }
+
+    pub fn pull(&mut self, rng: &mut ChaCha20Rng) -> Pull {
+        let table = &self.table;
+        self.pulls_done += 1;
+
+        // first listed pity rule wins when several are due on the same pull
+        let forced_rarity = table.pity.iter()
+            .zip(&self.pity_counters)
+            .find(|(rule, misses)| **misses >= rule.after)
+            .map(|(rule, _)| &rule.rarity);
+
+        let candidates: Vec<&Entry> = match forced_rarity {
+            Some(rarity) => table.entries.iter().filter(|entry| entry.rarity.as_ref() == Some(rarity)).collect(),
+            None => table.entries.iter().collect(),
+        };
+        let drop = table.roll(&candidates, None, rng);
+
+        for (rule, misses) in table.pity.iter().zip(self.pity_counters.iter_mut()) {
+            if drop.rarity.as_ref() == Some(&rule.rarity) {
+                *misses = 0;
+            } else {
+                *misses += 1;
+            }
+        }
+
+        let mut drops = vec![drop];
+        for guaranteed in &table.guaranteed {
+            if self.pulls_done.is_multiple_of(guaranteed.every) {
+                drops.push(Reward { item: guaranteed.item.clone(), rarity: None });
+            }
+        }
+
+        Pull { drops, pity_triggered: forced_rarity.is_some() }
+    }
+}
+
+struct ItemTally {
+    count: u64,
+    rarity: Option<String>,
+}
+
+fn format_cost(pulls: f64, cost_per_pull: Option<f64>) -> String {
+    match cost_per_pull {
+        Some(cost) => format!("{:.1} pulls ({:.0} cost)", pulls, pulls * cost),
+        None => format!("{:.1} pulls", pulls),
+    }
+}
+
+fn simulate_pulls(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let label = match &state.loot_session {
+        Some(session) => format!("Loot table file (blank to keep {})", session.path),
+        None => String::from("Loot table file (.toml or .json)"),
+    };
+    let path = prompt(terminal, state, &label)?;
+    let path = path.trim();
+    if !path.is_empty() || state.loot_session.is_none() {
+        state.loot_session = Some(Session::new(path.to_string(), load(path)?));
+    }
+
+    let pulls = parse_or_default(&prompt(terminal, state, "Number of pulls (blank for 1)")?, 1, 1..=MAX_PULLS, "pulls")?;
+    let Some(session) = state.loot_session.as_mut() else {
+        return Ok(());
+    };
+
+    let mut tallies: BTreeMap<String, ItemTally> = BTreeMap::new();
+    let mut pity_pulls = 0;
+    let mut last_pull = None;
+    for _ in 0..pulls {
+        let pull = session.pull(rng);
+        if pull.pity_triggered {
+            pity_pulls += 1;
+        }
+        for drop in &pull.drops {
+            let tally = tallies.entry(drop.item.clone()).or_insert(ItemTally { count: 0, rarity: drop.rarity.clone() });
+            tally.count += 1;
+        }
+        last_pull = Some(pull);
+    }
+
+    let title = session.table.name.clone().unwrap_or_else(|| session.path.clone());
+    let mut messages = Vec::new();
+    if pulls == 1 {
+        if let Some(pull) = last_pull {
+            let drops: Vec<String> = pull.drops.iter()
+                .map(|drop| match &drop.rarity {
+                    Some(rarity) => format!("{} ({})", drop.item, rarity),
+                    None => drop.item.clone(),
+                })
+                .collect();
+            let pity = if pull.pity_triggered { " [pity]" } else { "" };
+            messages.push(format!("{} pull #{}: {}{}", title, session.pulls_done, drops.join(", "), pity));
+        }
+    } else {
+        messages.push(format!("{}: {} pulls, {} forced by pity (expected costs ignore pity)", title, pulls, pity_pulls));
+        let cost_per_pull = session.table.cost_per_pull;
+        for (item, probability) in session.table.item_probabilities() {
+            let tally = tallies.get(&item);
+            let count = tally.map_or(0, |tally| tally.count);
+            let rarity = tally.and_then(|tally| tally.rarity.as_deref()).map(|rarity| format!(" ({})", rarity)).unwrap_or_default();
+            let simulated = if count > 0 {
+                format_cost(pulls as f64 / count as f64, cost_per_pull)
+            } else {
+                String::from("not obtained")
+            };
+            messages.push(format!(
+                "{}{}: {}x, expected {} vs simulated {} per copy",
+                item, rarity, count, format_cost(1.0 / probability, cost_per_pull), simulated
+            ));
+        }
+    }
+
+    for message in messages {
+        state.push_message_output(message);
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = simulate_pulls(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn parse(toml: &str) -> LootTable {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn rejects_pity_rarity_overridden_by_nested_table() {
+        let table = parse(r#"
+            pity = [{ rarity = "legendary", after = 3 }]
+            entries = [
+                { item = "sword", weight = 99.0, rarity = "common" },
+                { table = "chest", weight = 1.0, rarity = "legendary" },
+            ]
+            [tables]
+            chest = [
+                { item = "crown", weight = 1.0 },
+                { item = "boot", weight = 1.0, rarity = "common" },
+            ]
+        "#);
+        let error = table.validate().unwrap_err();
+        assert!(error.contains("\"common\""), "{}", error);
+    }
+
+    #[test]
+    fn forced_pull_resets_pity() {
+        let table = parse(r#"
+            pity = [{ rarity = "legendary", after = 3 }]
+            entries = [
+                { item = "sword", weight = 1000000.0, rarity = "common" },
+                { table = "chest", weight = 1.0, rarity = "legendary" },
+            ]
+            [tables]
+            chest = [
+                { item = "crown", weight = 1.0 },
+                { item = "ring", weight = 1.0, rarity = "legendary" },
+            ]
+        "#);
+        table.validate().unwrap();
+        let mut session = Session::new(String::new(), table);
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        for round in 0..5 {
+            for _ in 0..3 {
+                assert!(!session.pull(&mut rng).pity_triggered, "round {}", round);
+            }
+            let pull = session.pull(&mut rng);
+            assert!(pull.pity_triggered, "round {}", round);
+            assert_eq!(pull.drops[0].rarity.as_deref(), Some("legendary"));
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::{text::Line, DefaultTerminal};
+
+use crate::{prompt, prompt_count, words, State};
+
+const MAX_COUNT: usize = 1000;
+const OPENING: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+// sentence and paragraph lengths are drawn from a normal distribution, clamped to these bounds
+const SENTENCE_WORDS: (f64, f64, usize, usize) = (14.0, 5.0, 4, 36);
+const PARAGRAPH_SENTENCES: (f64, f64, usize, usize) = (5.0, 1.5, 2, 9);
+
+static LOREM_WORDS: &[&str] = &[
+    "a", "ac", "accumsan", "adipiscing", "aenean", "aliquam", "aliquet", "amet", "ante", "arcu", "at", "auctor",
+    "augue", "bibendum", "blandit", "commodo", "condimentum", "congue", "consectetur", "consequat", "convallis",
+    "cras", "cursus", "dapibus", "diam", "dictum", "dictumst", "dignissim", "dolor", "donec", "dui", "duis",
+    "egestas", "eget", "eleifend", "elementum", "elit", "enim", "erat", "eros", "est", "et", "etiam", "eu",
+    "euismod", "facilisi", "facilisis", "fames", "faucibus", "felis", "fermentum", "feugiat", "fringilla",
+    "fusce", "gravida", "habitant", "habitasse", "hac", "hendrerit", "iaculis", "id", "imperdiet", "in",
+    "integer", "interdum", "ipsum", "justo", "lacinia", "lacus", "laoreet", "lectus", "leo", "libero", "ligula",
+    "lobortis", "lorem", "luctus", "maecenas", "magna", "malesuada", "massa", "mattis", "mauris", "metus", "mi",
+    "molestie", "mollis", "morbi", "nam", "nec", "neque", "netus", "nibh", "nisi", "nisl", "non", "nulla",
+    "nullam", "nunc", "odio", "orci", "ornare", "pellentesque", "pharetra", "phasellus", "placerat", "platea",
+    "porta", "porttitor", "posuere", "potenti", "praesent", "pretium", "proin", "pulvinar", "purus", "quam",
+    "quis", "quisque", "rhoncus", "risus", "rutrum", "sagittis", "sapien", "scelerisque", "sed", "sem", "semper",
+    "senectus", "sit", "sodales", "sollicitudin", "suscipit", "suspendisse", "tellus", "tempor", "tempus",
+    "tincidunt", "tortor", "tristique", "turpis", "ullamcorper", "ultrices", "ultricies", "urna", "ut", "varius",
+    "vel", "velit", "venenatis", "vestibulum", "vitae", "vivamus", "viverra", "volutpat", "vulputate",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Source {
+    Lorem,
+    Wordlist,
+}
+
+#[derive(Clone, Copy)]
+pub enum Unit {
+    Words,
+    Sentences,
+    Paragraphs,
+}
+
+impl Source {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "" | "lorem" => Ok(Source::Lorem),
+            "words" | "wordlist" => Ok(Source::Wordlist),
+            other => Err(format!("unknown source \"{}\", expected lorem or words", other)),
+        }
+    }
+
+    fn word(&self, rng: &mut ChaCha20Rng) -> &'static str {
+        let list = match self {
+            Source::Lorem => LOREM_WORDS,
+            Source::Wordlist => words::wordlist,
+        };
+        list[rng.random_range(0..list.len())]
+    }
+}
+
+impl Unit {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "words" | "word" => Ok(Unit::Words),
+            "sentences" | "sentence" => Ok(Unit::Sentences),
+            "" | "paragraphs" | "paragraph" => Ok(Unit::Paragraphs),
+            other => Err(format!("unknown unit \"{}\", expected words, sentences or paragraphs", other)),
+        }
+    }
+}
+
+// Box-Muller, rounded and clamped
+fn normal_length(rng: &mut ChaCha20Rng, (mean, deviation, min, max): (f64, f64, usize, usize)) -> usize {
+    let (u, v): (f64, f64) = (1.0 - rng.random::<f64>(), rng.random());
+    let sample = mean + deviation * (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos();
+    (sample.round().max(0.0) as usize).clamp(min, max)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut characters = word.chars();
+    characters.next().map(|first| first.to_uppercase().chain(characters).collect()).unwrap_or_default()
+}
+
+// a capitalised run of words with the odd comma in longer sentences and mostly full stops
+fn sentence(rng: &mut ChaCha20Rng, source: Source, opening: bool) -> String {
+    if opening {
+        return format!("{}.", OPENING);
+    }
+    let length = normal_length(rng, SENTENCE_WORDS);
+    let mut words: Vec<String> = (0..length).map(|_| source.word(rng).to_string()).collect();
+    if length >= 8 {
+        for _ in 0..rng.random_range(0..=length / 8) {
+            let position = rng.random_range(2..length - 2);
+            if !words[position].ends_with(',') {
+                words[position].push(',');
+            }
+        }
+    }
+    let punctuation = match rng.random_range(0..100) {
+        0..=5 => '?',
+        6..=8 => '!',
+        _ => '.',
+    };
+    format!("{}{}", capitalize(&words.join(" ")), punctuation)
+}
+
+// each string is one block of output: the run of words, a sentence or a paragraph. lorem text
+// opens with the traditional "Lorem ipsum dolor sit amet" like most generators do
+pub fn generate(rng: &mut ChaCha20Rng, source: Source, unit: Unit, count: usize) -> Vec<String> {
+    let opening = source == Source::Lorem;
+    match unit {
+        Unit::Words => {
+            let mut words: Vec<&str> = if opening { OPENING.split(' ').take(count).collect() } else { Vec::new() };
+            while words.len() < count {
+                words.push(source.word(rng));
+            }
+            vec![words.join(" ").replace(',', "")]
+        }
+        Unit::Sentences => (0..count).map(|index| sentence(rng, source, opening && index == 0)).collect(),
+        Unit::Paragraphs => (0..count)
+            .map(|index| {
+                let sentences = normal_length(rng, PARAGRAPH_SENTENCES);
+                (0..sentences)
+                    .map(|position| sentence(rng, source, opening && index == 0 && position == 0))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect(),
+    }
+}
+
+// greedy word wrap, the output pane doesn't wrap on its own
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
+
+fn generate_lorem(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let source = Source::parse(&prompt(terminal, state, "Source: lorem or words (blank for lorem)")?)?;
+    let unit = Unit::parse(&prompt(terminal, state, "Unit: words, sentences, paragraphs (blank for paragraphs)")?)?;
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    // half the terminal minus margins, borders and the "[n] " prefix
+    let width = terminal.size().map(|size| size.width as usize / 2).unwrap_or(40).saturating_sub(10).max(20);
+    for block in generate(rng, source, unit, count) {
+        let mut lines = wrap(&block, width).into_iter();
+        state.push_message_output(lines.next().unwrap_or_default());
+        state.push_output_lines(lines.map(|line| Line::from(format!("    {}", line))).collect());
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_lorem(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
@@ -3,21 +3,52 @@ use rand::SeedableRng;
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     prelude::{Stylize, Alignment},
-    style::{palette::tailwind::GREEN, Color},
-    text::{Text, Line},
-    widgets::{Block, Borders, Paragraph, List, ListItem},
+    style::palette::tailwind::GREEN,
+    text::{Text, Line, Span},
+    widgets::{Block, Borders, Gauge, Paragraph, List, ListItem},
     Frame
 };
 use rand_chacha::ChaCha20Rng; // 20 round chacha, CSPRNG
 use std::{thread::{self}, time};
 use rand::{prelude::*};
 
+mod backoff;
+mod cards;
+mod chance;
+mod cli;
+mod codename;
+mod codename_corpus;
+mod dates;
+mod encoding;
+mod export;
+mod fake;
+mod fake_corpus;
+mod ids;
+mod loot;
+mod lorem;
+mod markov;
+mod maze;
+mod montecarlo;
+mod mnemonic;
+mod otp;
+mod palette;
+mod pins;
+mod pronounceable;
+mod qr;
+mod regex_gen;
+mod shamir;
+mod stochastic;
+mod template;
+mod tokens;
+mod tournament;
+mod wifi;
 mod words;
 
 const UPDATE_RATE_MILLIS: u64 = 90;
+const INPUT_CANCELLED: &str = "input cancelled";
+const MAX_OUTPUT_LINES: usize = 500;
 // TODO IDEAS :
 
-// "headless" non-TUI mode, with passable args but menu by default or something
 // proper output window scrolling
 
 struct InputLabelGuard {
@@ -58,24 +89,33 @@ struct State {
     input_mode: bool,
     input_string: String,
     input_label_text: String,
-    output_widget_messages: Vec<String>,
+    output_widget_messages: Vec<Line<'static>>,
+    loot_session: Option<loot::Session>,
+    card_deck: Option<cards::Deck>,
+    id_generator: ids::IdGenerator,
+    otp_config: Option<otp::OtpConfig>,
+    // label and completed ratio of a long running job, drawn as a gauge under the menu
+    progress: Option<(String, f64)>,
+    // a chart shown in place of the output list while it's open
+    plot: Option<stochastic::Plot>,
 }
 
 impl State {
     fn new() -> Self {
         Self {
-            menu_items: vec![
-                String::from("Coinflip"),
-                String::from("Percentage Chance Roll"),
-                String::from("Password Generator"),
-                String::from("Range Randomization")
-            ],
+            menu_items: MENU.iter().map(|(label, _)| label.to_string()).collect(),
             selected_index: 0,
             result_index: 0,
             input_mode: false,
             input_string: String::new(),
             input_label_text: String::from("Input"), // Shows prompt dialog labeled with "input" by default
             output_widget_messages: Vec::new(),
+            loot_session: None,
+            card_deck: None,
+            id_generator: ids::IdGenerator::default(),
+            otp_config: None,
+            progress: None,
+            plot: None,
         }
     }
 
@@ -96,11 +136,31 @@ impl State {
     }
 
     fn push_message_output(&mut self, msg: String) {
-        if self.output_widget_messages.len() > 17 {
-            self.output_widget_messages.clear();
+        self.push_styled_output(vec![Span::raw(msg)]);
+    }
+
+    // same as push_message_output but keeps per-span styling (coloured suits etc.)
+    fn push_styled_output(&mut self, spans: Vec<Span<'static>>) {
+        self.result_index += 1;
+
+        let mut line = vec![Span::raw(format!("[{:?}] ", self.result_index))];
+        line.extend(spans);
+        self.push_output_lines(vec![Line::from(line)]);
+    }
+
+    // unnumbered multi-line output such as QR codes, kept together so they are never split
+    fn push_output_lines(&mut self, lines: Vec<Line<'static>>) {
+        self.output_widget_messages.extend(lines);
+        let overflow = self.output_widget_messages.len().saturating_sub(MAX_OUTPUT_LINES);
+        self.output_widget_messages.drain(..overflow);
+    }
+
+    fn push_error_output(&mut self, error: String) {
+        if error == INPUT_CANCELLED {
+            self.push_message_output(error);
+        } else {
+            self.push_message_output(format!("ERROR: {}", error));
         }
-        self.result_index = self.result_index + 1;
-        self.output_widget_messages.push(format!("[{:?}] {}", self.result_index, msg));
     }
 }
 
@@ -143,7 +203,180 @@ fn prompt_user_input(terminal: &mut ratatui::Terminal<ratatui::backend::Crosster
     }
 }
 
+// prompt_user_input for handlers that bubble errors up with ?, Esc turns into Err(INPUT_CANCELLED)
+fn prompt(terminal: &mut ratatui::DefaultTerminal, state: &mut State, label: &str) -> Result<String, String> {
+    prompt_user_input(terminal, state, Some(label.to_string())).ok_or_else(|| INPUT_CANCELLED.to_string())
+}
+
+// blank input gives the default, anything else has to parse and land inside `range`.
+// `what` names the value in error messages
+fn parse_or_default<T>(input: &str, default: T, range: std::ops::RangeInclusive<T>, what: &str) -> Result<T, String>
+where
+    T: std::str::FromStr + PartialOrd + std::fmt::Display,
+    T::Err: std::fmt::Display,
+{
+    let value = match input.trim() {
+        "" => default,
+        value => value.parse::<T>().map_err(|e| format!("{}: {}", what, e))?,
+    };
+    if !range.contains(&value) {
+        return Err(format!("{} must be between {} and {}", what, range.start(), range.end()));
+    }
+    Ok(value)
+}
+
+// the "how many" question most generators ask, blank means one and anything past max is refused
+fn prompt_count(terminal: &mut ratatui::DefaultTerminal, state: &mut State, max: usize) -> Result<usize, String> {
+    parse_or_default(&prompt(terminal, state, "Count (blank for 1)")?, 1, 1..=max, "count")
+}
+
+// menu label and the screen it opens, the menu list is built from this so the two can't drift apart
+type Screen = fn(&mut ratatui::DefaultTerminal, &mut State, &mut ChaCha20Rng);
+
+const MENU: &[(&str, Screen)] = &[
+    ("Coinflip", coinflip),
+    ("Percentage Chance Roll", chance::run),
+    ("Password Generator", password), // memorable words, separators and digits
+    ("Range Randomization", range),
+    ("Loot Table Simulator", loot::run), // loot table / gacha simulator
+    ("Playing Cards", cards::run), // card deck draws and deals
+    ("ID Generator", ids::run), // uuid / ulid / nanoid / snowflake
+    ("Random Token", tokens::run), // raw bytes as hex/base64/etc
+    ("PIN Generator", pins::run), // pins with weak pattern rejection
+    ("TOTP/HOTP Secret", otp::run), // 2fa secret, otpauth uri and qr code
+    ("Wi-Fi Credentials", wifi::run), // wpa passphrase and join qr code
+    ("Recombine Shares", |terminal, state, _| shamir::run(terminal, state)), // rebuild a split password/token
+    ("BIP-39 Mnemonic", mnemonic::run), // bip-39 phrases and checksum validation
+    ("Pronounceable Password", pronounceable::run), // consonant/vowel passwords
+    ("Template Generator", template::run), // svc-{word}-{digit:4} style templates
+    ("Regex String Generator", regex_gen::run), // strings matching a regex subset
+    ("Fake Test Data", fake::run), // names, emails, addresses, ips, macs
+    ("Bulk Record Export", export::run), // schema driven csv/jsonl/sql files
+    ("Random Dates & Durations", dates::run), // timestamps in a range, weekday/business hour filters
+    ("Retry Backoff Schedule", backoff::run), // exponential backoff with jitter, load simulation
+    ("Colour Palettes", palette::run), // rgb/hsl/oklch swatches, harmonies, contrast pairs
+    ("Codename Generator", codename::run), // adjective-noun names for branches and environments
+    ("Markov Text Generator", markov::run), // order-n char/word chains from a corpus
+    ("Lorem Ipsum Text", lorem::run), // filler words, sentences and paragraphs
+    ("Monte Carlo Workbench", montecarlo::run), // multi-threaded simulations with a progress gauge
+    ("Random Walks & Processes", stochastic::run), // walks, brownian motion, markov chains, gbm charts
+    ("Maze Generator", maze::run), // backtracker, kruskal and wilson mazes
+    ("Tournament Brackets", tournament::run), // elimination brackets and round robin schedules
+];
+
+fn coinflip(_terminal: &mut ratatui::DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    state.push_message_output(rng.random_bool(0.5).to_string());
+}
+
+fn password(terminal: &mut ratatui::DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    let separators = vec!['!', '@', '#', '$', '%', '&', '*', '=', '.', '/', '-'];
+    let numbers = vec!['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+    if let Some(input) = prompt_user_input(terminal, state, Some(String::from("Enter complexity level (1-4)"))) {
+        match input.trim().parse::<usize>() {
+            Ok(level) => {
+                let mut password_parts = Vec::new();
+
+                // select amount of random words based on complexity
+                let num_words = match level {
+                    1 => 2,
+                    2 => 3,
+                    3 => 4,
+                    4 => 5,
+                    _ => 5,
+                };
+
+                let mut word_indices: Vec<usize> = (0..words::wordlist.len()).collect();
+                word_indices.shuffle(rng);
+
+                for &word_index in word_indices.iter().take(num_words) {
+                    let mut word = words::wordlist[word_index].to_string();
+                    // randomly capitalize some words for additional complexity
+                    if rng.random_bool(0.5) {
+                        let mut chars: Vec<char> = word.chars().collect();
+                        if chars[0] == 'i' || chars[0] == 'l' {
+                            continue; // dont captialize "i" or "l" because they are difficult to distinguish in some fonts, reduces entropy a little
+                        }
+                        chars[0] = chars[0].to_ascii_uppercase();
+                        word = chars.into_iter().collect();
+                    }
+                    password_parts.push(word);
+                }
+
+                let mut sep_indices: Vec<usize> = (0..separators.len()).collect();
+                sep_indices.shuffle(rng);
+
+                for i in 0..(password_parts.len() - 1) {
+                   // using modulo to cycle through seperators
+                   password_parts.insert(i * 2 + 1, separators[sep_indices[i % separators.len()]].to_string());
+                }
+
+                if level >= 2 {
+                    let num_count = match level {
+                        2 => 1,
+                        3 => 2,
+                        _ => 3,
+                    };
+                    for _ in 0..num_count {
+                        let pos = rng.random_range(0..=password_parts.len());
+                        let num_idx = rng.random_range(0..numbers.len());
+                        password_parts.insert(pos, numbers[num_idx].to_string());
+                    }
+                }
+
+                let generated_password = password_parts.join("");
+                state.push_message_output(format!("password: {}", generated_password));
+                if let Err(error) = shamir::offer_split(terminal, state, rng, generated_password.as_bytes()) {
+                    state.push_error_output(error);
+                }
+            }
+            Err(error) => {
+                state.push_message_output(format!("ERROR: {}", error));
+            }
+        }
+    } else {
+        state.push_message_output("input cancelled".to_string());
+    }
+}
+
+fn range(terminal: &mut ratatui::DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Some(input_min) = prompt_user_input(terminal, state, Some(String::from("Enter minimum number of range")))
+        && let Some(input_max) = prompt_user_input(terminal, state, Some(String::from("Enter maximum number of range"))) {
+        match (input_min.trim().parse::<u64>(), input_max.trim().parse::<u64>()) {
+            (Ok(min_int), Ok(max_int)) => {
+                if min_int < max_int {
+                    let answer = rng.random_range(min_int..max_int);
+                    state.push_message_output(format!("{}", answer));
+                }
+                else if min_int == max_int {
+                    state.push_message_output(String::from("ERROR: Minimum and maximum range values cannot be the same"));
+                }
+                else if min_int > max_int {
+                    state.push_message_output(String::from("ERROR: Minimum range value cannot be larger than maximum range value"));
+                }
+
+            },
+            (min_result, max_result) => {
+                let mut errors = Vec::new();
+
+                if let Err(e) = min_result {
+                    errors.push(format!("min : {}", e));
+                }
+                if let Err(e) = max_result {
+                    errors.push(format!("max : {}", e));
+                }
+            }
+        }
+    }
+}
+
 fn main() {
+    // any arguments mean headless mode, the menu is the default
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     cli_log::init_cli_log!();
     let mut terminal = ratatui::init();
     let mut state: State = State::new();
@@ -153,141 +386,47 @@ fn main() {
         terminal.draw(|frame| draw(frame, &state)).expect("failed to draw frame");
         thread::sleep(time::Duration::from_millis(UPDATE_RATE_MILLIS));
 
-        if state.input_mode == false {
-            if let Event::Key(key) = event::read().expect("failed to read event") {
-                match key.code {
-                    KeyCode::Char('q') => break,
-                    KeyCode::Up => state.select_previous(),
-                    KeyCode::Down => state.select_next(),
-                    KeyCode::Enter => {
-                        match state.selected_index {
-                            0 => { // coinflip
-                                state.push_message_output(rng.random_bool(0.5).to_string());
-                            }
-                            1 => { // percent chance roll
-                                if let Some(input) = prompt_user_input(&mut terminal, &mut state, Some(String::from("Enter percentage"))) {
-                                    match input.trim().parse::<f64>() {
-                                        Ok(value) => {
-                                            let pvalue = value / 100.0;
-                                            state.push_message_output(format!("Hit: {}", rng.random_bool(pvalue)));
-                                        }
-                                        Err(error) => {
-                                            state.push_message_output(format!("ERROR: {}", error));
-                                        }
-                                    }
-                                } else {
-                                    state.push_message_output("input cancelled".to_string());
-                                }
-                            }
-                            2 => { // memorable password generator
-                                let separators = vec!['!', '@', '#', '$', '%', '&', '*', '=', '.', '/', '-'];
-                                let numbers = vec!['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
-
-                                if let Some(input) = prompt_user_input(&mut terminal, &mut state, Some(String::from("Enter complexity level (1-4)"))) {
-                                    match input.trim().parse::<usize>() {
-                                        Ok(level) => {
-                                            let mut password_parts = Vec::new();
-
-                                            // select amount of random words based on complexity
-                                            let num_words = match level {
-                                                1 => 2,
-                                                2 => 3,
-                                                3 => 4,
-                                                4 => 5,
-                                                _ => 5,
-                                            };
-
-                                            let mut word_indices: Vec<usize> = (0..words::wordlist.len()).collect();
-                                            word_indices.shuffle(&mut rng);
-
-                                            for i in 0..num_words {
-                                                let mut word = words::wordlist[word_indices[i]].to_string();
-                                                // randomly capitalize some words for additional complexity
-                                                if rng.random_bool(0.5) {
-                                                    let mut chars: Vec<char> = word.chars().collect();
-                                                    if chars[0] == 'i' || chars[0] == 'l' {
-                                                        continue; // dont captialize "i" or "l" because they are difficult to distinguish in some fonts, reduces entropy a little
-                                                    }
-                                                    chars[0] = chars[0].to_ascii_uppercase();
-                                                    word = chars.into_iter().collect();
-                                                }
-                                                password_parts.push(word);
-                                            }
-
-                                            let mut sep_indices: Vec<usize> = (0..separators.len()).collect();
-                                            sep_indices.shuffle(&mut rng);
-
-                                            for i in 0..(password_parts.len() - 1) {
-                                               // using modulo to cycle through seperators
-                                               password_parts.insert(i * 2 + 1, separators[sep_indices[i % separators.len()]].to_string());
-                                            }
-
-                                            if level >= 2 {
-                                                let num_count = match level {
-                                                    2 => 1,
-                                                    3 => 2,
-                                                    _ => 3,
-                                                };
-                                                for _ in 0..num_count {
-                                                    let pos = rng.random_range(0..=password_parts.len());
-                                                    let num_idx = rng.random_range(0..numbers.len());
-                                                    password_parts.insert(pos, numbers[num_idx].to_string());
-                                                }
-                                            }
-
-                                            let generated_password = password_parts.join("");
-                                            state.push_message_output(format!("password: {}", generated_password));                                        }
-                                        Err(error) => {
-                                            state.push_message_output(format!("ERROR: {}", error));
-                                        }
-                                    }
-                                } else {
-                                    state.push_message_output("input cancelled".to_string());
-                                }
-                            }
-                            3 => { // range randomization
-                                if let Some(input_min) = prompt_user_input(&mut terminal, &mut state, Some(String::from("Enter minimum number of range"))) {
-                                    if let Some(input_max) = prompt_user_input(&mut terminal, &mut state, Some(String::from("Enter maximum number of range"))) {
-
-                                        match (input_min.trim().parse::<u64>(), input_max.trim().parse::<u64>()) {
-                                            (Ok(min_int), Ok(max_int)) => {
-                                                if min_int < max_int {
-                                                    let answer = rng.random_range(min_int..max_int);
-                                                    state.push_message_output(format!("{}", answer));
-                                                }
-                                                else if min_int == max_int {
-                                                    state.push_message_output(String::from("ERROR: Minimum and maximum range values cannot be the same"));
-                                                }
-                                                else if min_int > max_int {
-                                                    state.push_message_output(String::from("ERROR: Minimum range value cannot be larger than maximum range value"));
-                                                }
-
-                                            },
-                                            (min_result, max_result) => {
-                                                let mut errors = Vec::new();
-
-                                                if let Err(e) = min_result {
-                                                    errors.push(format!("min : {}", e));
-                                                }
-                                                if let Err(e) = max_result {
-                                                    errors.push(format!("max : {}", e));
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            _ => state.push_message_output("Severe error".to_string()),
-                        }
+        if !state.input_mode
+            && let Event::Key(key) = event::read().expect("failed to read event") {
+            match key.code {
+                KeyCode::Char('q') => break,
+                KeyCode::Up => state.select_previous(),
+                KeyCode::Down => state.select_next(),
+                KeyCode::Enter => {
+                    match MENU.get(state.selected_index) {
+                        Some((_, screen)) => screen(&mut terminal, &mut state, &mut rng),
+                        None => state.push_message_output("Severe error".to_string()),
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
     }
     ratatui::restore();
 }
 
+// inner width and height of the output pane as laid out by `draw`: the right half inside the
+// outer margin, minus its own border. None when the terminal size can't be read
+fn output_pane_size(terminal: &ratatui::DefaultTerminal) -> Option<(usize, usize)> {
+    let size = terminal.size().ok()?;
+    let width = (size.width as usize).saturating_sub(2) / 2;
+    let height = (size.height as usize).saturating_sub(2);
+    Some((width.saturating_sub(2), height.saturating_sub(2)))
+}
+
+// for drawings that are useless once clipped, like mazes and brackets. the error gives both
+// sizes so the caller can send the user to an export file instead
+fn fits_output_pane(terminal: &ratatui::DefaultTerminal, lines: &[Line]) -> Result<(), String> {
+    let Some((pane_width, pane_height)) = output_pane_size(terminal) else {
+        return Ok(());
+    };
+    let width = lines.iter().map(Line::width).max().unwrap_or(0);
+    if width > pane_width || lines.len() > pane_height {
+        return Err(format!("{}x{} characters but the output pane only shows {}x{}", width, lines.len(), pane_width, pane_height));
+    }
+    Ok(())
+}
+
 fn draw(frame: &mut Frame, state: &State) {
     let outer_layout = Layout::default()
         .margin(1)
@@ -304,7 +443,7 @@ fn draw(frame: &mut Frame, state: &State) {
         .map(|(index, item)| {
             if index == state.selected_index {
                 Line::from(format!("> {}", item))
-                    .style(Color::from(GREEN.c300))
+                    .style(GREEN.c300)
                     .bold()
             } else {
                 Line::from(format!("  {}", item))
@@ -317,7 +456,7 @@ fn draw(frame: &mut Frame, state: &State) {
         .block(menu_block)
         .alignment(Alignment::Center);
 
-    if state.input_mode == true {
+    if state.input_mode {
         let input_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![
@@ -331,13 +470,37 @@ fn draw(frame: &mut Frame, state: &State) {
         let input_widget = Paragraph::new(input_text)
             .block(Block::new().title("Input Prompt").borders(Borders::ALL));
         frame.render_widget(input_widget, input_layout[1]);
+    } else if let Some((label, ratio)) = &state.progress {
+        let progress_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(outer_layout[0]);
+        frame.render_widget(menu_widget, progress_layout[0]);
+
+        let gauge_widget = Gauge::default()
+            .block(Block::new().title(label.clone()).borders(Borders::ALL))
+            .gauge_style(GREEN.c300)
+            .ratio(ratio.clamp(0.0, 1.0));
+        frame.render_widget(gauge_widget, progress_layout[1]);
     } else {
         frame.render_widget(menu_widget, outer_layout[0]);
     }
 
+    if let Some(plot) = &state.plot {
+        frame.render_widget(plot.chart(), outer_layout[1]);
+        return;
+    }
+
+    // only the newest lines that fit are shown, older ones scroll off the top
+    let visible_lines = outer_layout[1].height.saturating_sub(2) as usize;
+    let skipped_lines = state.output_widget_messages.len().saturating_sub(visible_lines);
     let output_list: Vec<ListItem> = state.output_widget_messages
         .iter()
-        .map(|msg| ListItem::new(msg.as_str()))
+        .skip(skipped_lines)
+        .map(|msg| ListItem::new(msg.clone()))
         .collect();
 
     frame.render_widget(
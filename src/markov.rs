@@ -0,0 +1,176 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{prompt, prompt_count, words, State};
+
+const MAX_COUNT: usize = 16;
+const MAX_ORDER: usize = 6;
+const MAX_ATTEMPTS: u32 = 1000;
+// char mode output bounds, words outside them are thrown away and drawn again
+const MIN_WORD_CHARS: usize = 4;
+const MAX_WORD_CHARS: usize = 12;
+const MAX_SENTENCE_WORDS: usize = 40;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Unit {
+    Chars,
+    Words,
+}
+
+// each state is the last `order` tokens, an empty token pads the start and None marks the end
+pub struct Chain {
+    unit: Unit,
+    order: usize,
+    transitions: HashMap<Vec<String>, Vec<(Option<String>, u32)>>,
+    samples: HashSet<String>,
+}
+
+impl Unit {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "" | "chars" | "char" | "names" => Ok(Unit::Chars),
+            "words" | "word" | "sentences" => Ok(Unit::Words),
+            other => Err(format!("unknown mode \"{}\", expected chars or words", other)),
+        }
+    }
+
+    fn default_order(&self) -> usize {
+        match self {
+            Unit::Chars => 3,
+            Unit::Words => 2,
+        }
+    }
+}
+
+// char mode learns from every whitespace separated word, word mode from sentences ending in . ! or ?
+fn samples(unit: Unit, corpus: &str) -> Vec<Vec<String>> {
+    match unit {
+        Unit::Chars => corpus.split_whitespace()
+            .map(|word| word.chars().filter(|character| character.is_alphabetic() || *character == '\'').collect::<String>())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.chars().map(String::from).collect())
+            .collect(),
+        Unit::Words => {
+            let mut sentences = Vec::new();
+            let mut sentence = Vec::new();
+            for word in corpus.split_whitespace() {
+                sentence.push(word.to_string());
+                if word.ends_with(['.', '!', '?']) {
+                    sentences.push(std::mem::take(&mut sentence));
+                }
+            }
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            sentences
+        }
+    }
+}
+
+impl Chain {
+    pub fn train(unit: Unit, order: usize, corpus: &str) -> Result<Self, String> {
+        if order == 0 || order > MAX_ORDER {
+            return Err(format!("order must be between 1 and {}", MAX_ORDER));
+        }
+        let samples = samples(unit, corpus);
+        if samples.is_empty() {
+            return Err(String::from("the corpus has nothing to learn from"));
+        }
+
+        let mut transitions: HashMap<Vec<String>, Vec<(Option<String>, u32)>> = HashMap::new();
+        for sample in &samples {
+            let mut window = vec![String::new(); order];
+            let tokens = sample.iter().cloned().map(Some).chain(std::iter::once(None));
+            for token in tokens {
+                let followers = transitions.entry(window.clone()).or_default();
+                match followers.iter_mut().find(|(next, _)| *next == token) {
+                    Some((_, count)) => *count += 1,
+                    None => followers.push((token.clone(), 1)),
+                }
+                if let Some(token) = token {
+                    window.remove(0);
+                    window.push(token);
+                }
+            }
+        }
+        let joiner = if unit == Unit::Chars { "" } else { " " };
+        let samples = samples.iter().map(|sample| sample.join(joiner).to_lowercase()).collect();
+        Ok(Chain { unit, order, transitions, samples })
+    }
+
+    fn walk(&self, rng: &mut ChaCha20Rng, limit: usize) -> Vec<String> {
+        let mut window = vec![String::new(); self.order];
+        let mut output = Vec::new();
+        while output.len() < limit {
+            let Some(followers) = self.transitions.get(&window) else { break };
+            let total: u32 = followers.iter().map(|(_, count)| count).sum();
+            let mut pick = rng.random_range(0..total);
+            let next = followers.iter()
+                .find(|(_, count)| {
+                    if pick < *count {
+                        return true;
+                    }
+                    pick -= count;
+                    false
+                })
+                .and_then(|(next, _)| next.clone());
+            let Some(next) = next else { break };
+            window.remove(0);
+            window.push(next.clone());
+            output.push(next);
+        }
+        output
+    }
+
+    // char mode only keeps words of a sensible length that aren't copied straight from the corpus
+    pub fn generate(&self, rng: &mut ChaCha20Rng) -> Result<String, String> {
+        match self.unit {
+            Unit::Chars => {
+                for _ in 0..MAX_ATTEMPTS {
+                    let word = self.walk(rng, MAX_WORD_CHARS + 1).concat();
+                    let length = word.chars().count();
+                    if (MIN_WORD_CHARS..=MAX_WORD_CHARS).contains(&length) && !self.samples.contains(&word.to_lowercase()) {
+                        return Ok(word);
+                    }
+                }
+                Err(String::from("couldn't find a new word, try a lower order or a bigger corpus"))
+            }
+            Unit::Words => Ok(self.walk(rng, MAX_SENTENCE_WORDS).join(" ")),
+        }
+    }
+}
+
+fn generate_markov(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let unit = Unit::parse(&prompt(terminal, state, "Mode: chars (words, names) or words (sentences)")?)?;
+
+    let path = prompt(terminal, state, "Corpus file (blank for the built-in wordlist)")?;
+    let corpus = match path.trim() {
+        "" if unit == Unit::Words => return Err(String::from("sentence mode needs a corpus file of prose")),
+        "" => words::wordlist.join(" "),
+        path => std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?,
+    };
+
+    let input = prompt(terminal, state, &format!("Order (blank for {})", unit.default_order()))?;
+    let order = match input.trim() {
+        "" => unit.default_order(),
+        value => value.parse::<usize>().map_err(|e| e.to_string())?,
+    };
+    let chain = Chain::train(unit, order, &corpus)?;
+
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for _ in 0..count {
+        let value = chain.generate(rng)?;
+        state.push_message_output(value);
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_markov(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
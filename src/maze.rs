@@ -0,0 +1,359 @@
+use std::{collections::VecDeque, io::Write};
+
+use rand::{seq::SliceRandom, Rng};
+use rand_chacha::ChaCha20Rng;
+use ratatui::{
+    style::{Color, Stylize},
+    text::{Line, Span},
+    DefaultTerminal,
+};
+
+use crate::{cli, fits_output_pane, prompt, State};
+
+const MAX_SIDE: usize = 100;
+const PATH_MARK: char = '•';
+
+#[derive(Clone, Copy)]
+pub enum Algorithm {
+    Backtracker,
+    Kruskal,
+    Wilson,
+}
+
+// a perfect maze over a grid of cells, stored as which passages are open. the entrance is
+// the top of the top-left cell and the exit the bottom of the bottom-right one
+pub struct Maze {
+    width: usize,
+    height: usize,
+    // open to the cell on the right / below, indexed by y * width + x
+    right: Vec<bool>,
+    down: Vec<bool>,
+}
+
+impl Algorithm {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "" | "backtracker" | "dfs" => Ok(Algorithm::Backtracker),
+            "kruskal" => Ok(Algorithm::Kruskal),
+            "wilson" => Ok(Algorithm::Wilson),
+            other => Err(format!("unknown algorithm \"{}\", expected backtracker, kruskal or wilson", other)),
+        }
+    }
+}
+
+// union-find with path halving, for Kruskal
+fn find(parents: &mut [usize], mut cell: usize) -> usize {
+    while parents[cell] != cell {
+        parents[cell] = parents[parents[cell]];
+        cell = parents[cell];
+    }
+    cell
+}
+
+impl Maze {
+    fn neighbours(&self, cell: usize) -> Vec<usize> {
+        let (x, y) = (cell % self.width, cell / self.width);
+        let mut neighbours = Vec::with_capacity(4);
+        if x > 0 { neighbours.push(cell - 1) }
+        if x + 1 < self.width { neighbours.push(cell + 1) }
+        if y > 0 { neighbours.push(cell - self.width) }
+        if y + 1 < self.height { neighbours.push(cell + self.width) }
+        neighbours
+    }
+
+    fn carve(&mut self, a: usize, b: usize) {
+        let (low, high) = (a.min(b), a.max(b));
+        if high == low + 1 {
+            self.right[low] = true;
+        } else {
+            self.down[low] = true;
+        }
+    }
+
+    fn is_open(&self, a: usize, b: usize) -> bool {
+        let (low, high) = (a.min(b), a.max(b));
+        if high == low + 1 { self.right[low] } else { self.down[low] }
+    }
+
+    pub fn generate(rng: &mut ChaCha20Rng, algorithm: Algorithm, width: usize, height: usize) -> Self {
+        let cells = width * height;
+        let mut maze = Maze { width, height, right: vec![false; cells], down: vec![false; cells] };
+        match algorithm {
+            // long winding corridors, few dead ends
+            Algorithm::Backtracker => {
+                let mut visited = vec![false; cells];
+                let mut stack = vec![rng.random_range(0..cells)];
+                visited[stack[0]] = true;
+                while let Some(&cell) = stack.last() {
+                    let unvisited: Vec<usize> = maze.neighbours(cell).into_iter().filter(|next| !visited[*next]).collect();
+                    if unvisited.is_empty() {
+                        stack.pop();
+                        continue;
+                    }
+                    let next = unvisited[rng.random_range(0..unvisited.len())];
+                    maze.carve(cell, next);
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+            // every wall in random order, knocked down when it joins two separate regions
+            Algorithm::Kruskal => {
+                let mut walls: Vec<(usize, usize)> = (0..cells)
+                    .flat_map(|cell| {
+                        let right = (cell % width + 1 < width).then_some((cell, cell + 1));
+                        let down = (cell / width + 1 < height).then_some((cell, cell + width));
+                        right.into_iter().chain(down)
+                    })
+                    .collect();
+                walls.shuffle(rng);
+                let mut parents: Vec<usize> = (0..cells).collect();
+                for (a, b) in walls {
+                    let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+                    if root_a != root_b {
+                        parents[root_a] = root_b;
+                        maze.carve(a, b);
+                    }
+                }
+            }
+            // loop-erased random walks, every spanning tree is equally likely
+            Algorithm::Wilson => {
+                let mut in_tree = vec![false; cells];
+                in_tree[rng.random_range(0..cells)] = true;
+                let mut next_step = vec![0usize; cells];
+                for start in 0..cells {
+                    // walking over the same cell again overwrites its exit, which erases the loop
+                    let mut cell = start;
+                    while !in_tree[cell] {
+                        let neighbours = maze.neighbours(cell);
+                        next_step[cell] = neighbours[rng.random_range(0..neighbours.len())];
+                        cell = next_step[cell];
+                    }
+                    let mut cell = start;
+                    while !in_tree[cell] {
+                        in_tree[cell] = true;
+                        maze.carve(cell, next_step[cell]);
+                        cell = next_step[cell];
+                    }
+                }
+            }
+        }
+        maze
+    }
+
+    // breadth first from the entrance, the maze is perfect so this is the only path
+    pub fn solve(&self) -> Vec<bool> {
+        let cells = self.width * self.height;
+        let mut previous = vec![usize::MAX; cells];
+        let mut queue = VecDeque::from([0]);
+        previous[0] = 0;
+        while let Some(cell) = queue.pop_front() {
+            for next in self.neighbours(cell) {
+                if previous[next] == usize::MAX && self.is_open(cell, next) {
+                    previous[next] = cell;
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut on_path = vec![false; cells];
+        let mut cell = cells - 1;
+        on_path[cell] = true;
+        while cell != 0 {
+            cell = previous[cell];
+            on_path[cell] = true;
+        }
+        on_path
+    }
+
+    // cells are three characters wide so the maze doesn't look squashed in a terminal
+    pub fn render(&self, solution: Option<&[bool]>) -> Vec<String> {
+        let (width, height) = (self.width, self.height);
+        let cell = |x: usize, y: usize| y * width + x;
+        // is there a wall above cell (x, y), and left of it; out of range cells are outside the maze
+        let wall_above = |x: usize, y: usize| {
+            !((y == 0 && x == 0) || (y == height && x == width - 1) || (y > 0 && y < height && self.down[cell(x, y - 1)]))
+        };
+        let wall_left = |x: usize, y: usize| x == 0 || x == width || !self.right[cell(x - 1, y)];
+        let on_path = |x: usize, y: usize| solution.is_some_and(|path| path[cell(x, y)]);
+
+        let mut lines = Vec::with_capacity(height * 2 + 1);
+        for y in 0..=height {
+            let mut line = String::new();
+            for x in 0..=width {
+                let up = y > 0 && wall_left(x, y - 1);
+                let down = y < height && wall_left(x, y);
+                let left = x > 0 && wall_above(x - 1, y);
+                let right = x < width && wall_above(x, y);
+                line.push(corner(up, down, left, right));
+                if x < width {
+                    line.push_str(if right { "───" } else { "   " });
+                }
+            }
+            lines.push(line);
+            if y == height {
+                break;
+            }
+
+            let mut line = String::new();
+            for x in 0..=width {
+                line.push(if wall_left(x, y) { '│' } else { ' ' });
+                if x < width {
+                    line.push_str(if on_path(x, y) { " • " } else { "   " });
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+fn corner(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => ' ',
+        (true, true, false, false) | (true, false, false, false) | (false, true, false, false) => '│',
+        (false, false, true, true) | (false, false, true, false) | (false, false, false, true) => '─',
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        (true, true, false, true) => '├',
+        (true, true, true, false) => '┤',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (true, true, true, true) => '┼',
+    }
+}
+
+fn path_spans(line: &str) -> Line<'static> {
+    let spans: Vec<Span<'static>> = line.split_inclusive(PATH_MARK)
+        .flat_map(|part| match part.strip_suffix(PATH_MARK) {
+            Some(rest) => vec![Span::raw(rest.to_string()), Span::raw(PATH_MARK.to_string()).fg(Color::LightGreen).bold()],
+            None => vec![Span::raw(part.to_string())],
+        })
+        .collect();
+    Line::from(spans)
+}
+
+fn generate_maze(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let algorithm = Algorithm::parse(&prompt(terminal, state, "Algorithm: backtracker, kruskal, wilson")?)?;
+
+    let input = prompt(terminal, state, "Size as WIDTHxHEIGHT (blank for 12x8)")?;
+    let (width, height) = match input.trim() {
+        "" => (12, 8),
+        size => {
+            let (width, height) = size.split_once(['x', 'X']).ok_or("size is written like 12x8")?;
+            let width = width.trim().parse::<usize>().map_err(|e| format!("width: {}", e))?;
+            let height = height.trim().parse::<usize>().map_err(|e| format!("height: {}", e))?;
+            (width, height)
+        }
+    };
+    if !(2..=MAX_SIDE).contains(&width) || !(2..=MAX_SIDE).contains(&height) {
+        return Err(format!("width and height must be between 2 and {}", MAX_SIDE));
+    }
+
+    let show_solution = prompt(terminal, state, "Show the solution? (y/N)")?.trim().eq_ignore_ascii_case("y");
+    let path = prompt(terminal, state, "Export to text file (blank to skip)")?;
+
+    let maze = Maze::generate(rng, algorithm, width, height);
+    let solution = show_solution.then(|| maze.solve());
+    let lines = maze.render(solution.as_deref());
+
+    if !path.trim().is_empty() {
+        let mut output = cli::open_output(Some(path.trim()))?;
+        let io_error = |e: std::io::Error| e.to_string();
+        for line in &lines {
+            writeln!(output, "{}", line).map_err(io_error)?;
+        }
+        output.flush().map_err(io_error)?;
+    }
+
+    let saved = if path.trim().is_empty() { String::new() } else { format!(", saved to {}", path.trim()) };
+    state.push_message_output(format!("{}x{} maze{}", width, height, saved));
+    let lines: Vec<Line<'static>> = lines.iter().map(|line| path_spans(line)).collect();
+    // a clipped maze can't be followed by eye, large ones are only useful in the file
+    match fits_output_pane(terminal, &lines) {
+        Ok(()) => state.push_output_lines(lines),
+        Err(size) if saved.is_empty() => return Err(format!("the maze is {}, export it to a text file to see all of it", size)),
+        Err(size) => state.push_message_output(format!("the maze is {}, open {} to see all of it", size, path.trim())),
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_maze(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    const ALGORITHMS: [Algorithm; 3] = [Algorithm::Backtracker, Algorithm::Kruskal, Algorithm::Wilson];
+
+    fn reachable(maze: &Maze) -> usize {
+        let mut seen = vec![false; maze.width * maze.height];
+        let mut stack = vec![0];
+        seen[0] = true;
+        while let Some(cell) = stack.pop() {
+            for next in maze.neighbours(cell) {
+                if !seen[next] && maze.is_open(cell, next) {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen.iter().filter(|seen| **seen).count()
+    }
+
+    #[test]
+    fn every_algorithm_makes_a_perfect_maze() {
+        let mut rng = ChaCha20Rng::seed_from_u64(9);
+        for algorithm in ALGORITHMS {
+            for (width, height) in [(2, 2), (2, 9), (12, 8), (31, 17)] {
+                let maze = Maze::generate(&mut rng, algorithm, width, height);
+                let cells = width * height;
+                // connected with exactly cells - 1 passages is a spanning tree: no loops, no islands
+                let passages = maze.right.iter().chain(&maze.down).filter(|open| **open).count();
+                assert_eq!(passages, cells - 1, "{}x{}", width, height);
+                assert_eq!(reachable(&maze), cells, "{}x{}", width, height);
+            }
+        }
+    }
+
+    #[test]
+    fn solution_connects_entrance_to_exit() {
+        let mut rng = ChaCha20Rng::seed_from_u64(4);
+        for algorithm in ALGORITHMS {
+            let maze = Maze::generate(&mut rng, algorithm, 20, 15);
+            let path = maze.solve();
+            let last = path.len() - 1;
+            assert!(path[0] && path[last]);
+            // a simple path: the two ends have one open neighbour on it, every other cell two
+            for cell in (0..path.len()).filter(|cell| path[*cell]) {
+                let links = maze.neighbours(cell).into_iter().filter(|next| path[*next] && maze.is_open(cell, *next)).count();
+                let expected = if cell == 0 || cell == last { 1 } else { 2 };
+                assert_eq!(links, expected, "cell {}", cell);
+            }
+        }
+    }
+
+    #[test]
+    fn renders_a_fixed_maze() {
+        let maze = Maze { width: 2, height: 1, right: vec![true, false], down: vec![false, false] };
+        assert_eq!(maze.render(None), ["│   ────┐", "│       │", "└────   │"]);
+        assert_eq!(maze.render(Some(&maze.solve())), ["│   ────┐", "│ •   • │", "└────   │"]);
+    }
+
+    #[test]
+    fn rendered_size_follows_the_grid() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let maze = Maze::generate(&mut rng, Algorithm::Kruskal, 100, 100);
+        let path = maze.solve();
+        let lines = maze.render(Some(&path));
+        assert_eq!(lines.len(), 201);
+        assert!(lines.iter().all(|line| line.chars().count() == 401));
+        let marks: usize = lines.iter().map(|line| line.matches(PATH_MARK).count()).sum();
+        assert_eq!(marks, path.iter().filter(|on_path| **on_path).count());
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::LazyLock;
+
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+use sha2::{Digest, Sha256};
+
+use crate::{prompt, tokens, State};
+
+// the official BIP-39 english list, one word per line in sorted order
+static BIP39_ENGLISH: LazyLock<Vec<&'static str>> = LazyLock::new(|| include_str!("bip39_english.txt").lines().collect());
+
+const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+fn sha256(bytes: &[u8]) -> Vec<u8> {
+    Sha256::digest(bytes).to_vec()
+}
+
+fn bit(bytes: &[u8], index: usize) -> u16 {
+    ((bytes[index / 8] >> (7 - index % 8)) & 1) as u16
+}
+
+// entropy followed by its first len/32 sha256 bits, read off 11 bits per word
+pub fn from_entropy(entropy: &[u8]) -> Result<Vec<&'static str>, String> {
+    if !(16..=32).contains(&entropy.len()) || !entropy.len().is_multiple_of(4) {
+        return Err(String::from("entropy must be 128 to 256 bits in steps of 32"));
+    }
+    let mut bytes = entropy.to_vec();
+    bytes.extend(sha256(entropy));
+
+    let total_bits = entropy.len() * 8 + entropy.len() / 4;
+    let words = (0..total_bits / 11)
+        .map(|word| {
+            let index = (0..11).fold(0, |index, offset| (index << 1) | bit(&bytes, word * 11 + offset));
+            BIP39_ENGLISH[index as usize]
+        })
+        .collect();
+    Ok(words)
+}
+
+pub fn generate(rng: &mut ChaCha20Rng, word_count: usize) -> Result<Vec<&'static str>, String> {
+    if !WORD_COUNTS.contains(&word_count) {
+        return Err(String::from("word count must be 12, 15, 18, 21 or 24"));
+    }
+    // 33 bits of phrase for every 32 bits of entropy
+    let entropy = tokens::random_bytes(rng, word_count * 11 * 32 / 33 / 8);
+    from_entropy(&entropy)
+}
+
+// checks every word is on the list and the trailing checksum bits match
+pub fn validate(phrase: &str) -> Result<(), String> {
+    let words: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+    if !WORD_COUNTS.contains(&words.len()) {
+        return Err(format!("{} words, a mnemonic has 12, 15, 18, 21 or 24", words.len()));
+    }
+
+    let mut bits = Vec::with_capacity(words.len() * 11);
+    for (position, word) in words.iter().enumerate() {
+        let index = BIP39_ENGLISH.binary_search(&word.as_str())
+            .map_err(|_| format!("word {} \"{}\" is not in the BIP-39 english list", position + 1, word))?;
+        bits.extend((0..11).rev().map(|offset| (index >> offset) & 1 == 1));
+    }
+
+    let checksum_bits = bits.len() / 33;
+    let entropy: Vec<u8> = bits[..bits.len() - checksum_bits]
+        .chunks(8)
+        .map(|chunk| chunk.iter().fold(0, |byte, &bit| (byte << 1) | bit as u8))
+        .collect();
+    let hash = sha256(&entropy);
+    let expected = (0..checksum_bits).all(|index| bits[bits.len() - checksum_bits + index] == (bit(&hash, index) == 1));
+    if !expected {
+        return Err(String::from("checksum mismatch, a word is wrong or out of order"));
+    }
+    Ok(())
+}
+
+fn mnemonic_action(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let input = prompt(terminal, state, "Mnemonic: 12/15/18/21/24 words or check (blank for 12)")?;
+    match input.trim().to_lowercase().as_str() {
+        "check" | "validate" => {
+            let phrase = prompt(terminal, state, "Mnemonic to check")?;
+            validate(&phrase)?;
+            state.push_message_output(String::from("mnemonic is valid, checksum matches"));
+        }
+        count => {
+            let word_count = match count {
+                "" => 12,
+                value => value.parse::<usize>().map_err(|e| e.to_string())?,
+            };
+            let words = generate(rng, word_count)?;
+            state.push_message_output(format!("mnemonic: {}", words.join(" ")));
+            state.push_message_output(format!("{} bits of entropy + {} checksum bits", word_count * 32 / 3, word_count / 3));
+        }
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = mnemonic_action(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap()).collect()
+    }
+
+    // english vectors from the trezor reference implementation
+    const VECTORS: [(&str, &str); 9] = [
+        ("00000000000000000000000000000000", "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"),
+        ("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f", "legal winner thank year wave sausage worth useful legal winner thank yellow"),
+        ("80808080808080808080808080808080", "letter advice cage absurd amount doctor acoustic avoid letter advice cage above"),
+        ("ffffffffffffffffffffffffffffffff", "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"),
+        ("000000000000000000000000000000000000000000000000", "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent"),
+        ("0000000000000000000000000000000000000000000000000000000000000000", "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art"),
+        ("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote"),
+        ("9e885d952ad362caeb4efe34a8e91bd2", "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"),
+        ("68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c", "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length"),
+    ];
+
+    #[test]
+    fn entropy_matches_bip39_vectors() {
+        for (entropy, phrase) in VECTORS {
+            assert_eq!(from_entropy(&unhex(entropy)).unwrap().join(" "), phrase);
+        }
+    }
+
+    #[test]
+    fn vectors_validate() {
+        for (_, phrase) in VECTORS {
+            assert_eq!(validate(phrase), Ok(()));
+        }
+    }
+
+    #[test]
+    fn bad_checksum_is_rejected() {
+        assert!(validate(&["abandon"; 12].join(" ")).is_err());
+        assert!(validate("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo").is_err());
+    }
+}
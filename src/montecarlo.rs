@@ -0,0 +1,243 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{self, Event, KeyCode};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{draw, parse_or_default, prompt, State, UPDATE_RATE_MILLIS};
+
+const DEFAULT_ITERATIONS: u64 = 10_000_000;
+const MAX_ITERATIONS: u64 = 100_000_000_000;
+// workers report progress and check for cancellation once per chunk
+const CHUNK_ITERATIONS: u64 = 50_000;
+// two sided 95% normal quantile
+const Z_95: f64 = 1.959964;
+
+#[derive(Clone, Copy)]
+pub enum Comparison {
+    AtLeast,
+    Above,
+    AtMost,
+    Below,
+    Exactly,
+}
+
+// every trial produces a number and the estimate is their mean, so a yes/no experiment
+// estimates a probability and a counting one estimates an expected value
+#[derive(Clone, Copy)]
+pub enum Experiment {
+    Dice { count: u32, sides: u32, comparison: Comparison, target: u64 },
+    Pairs { hand: usize },
+    Pi,
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct Totals {
+    pub iterations: u64,
+    pub sum: f64,
+    pub sum_squares: f64,
+}
+
+impl Comparison {
+    fn holds(&self, value: u64, target: u64) -> bool {
+        match self {
+            Comparison::AtLeast => value >= target,
+            Comparison::Above => value > target,
+            Comparison::AtMost => value <= target,
+            Comparison::Below => value < target,
+            Comparison::Exactly => value == target,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::AtLeast => ">=",
+            Comparison::Above => ">",
+            Comparison::AtMost => "<=",
+            Comparison::Below => "<",
+            Comparison::Exactly => "=",
+        }
+    }
+}
+
+impl Experiment {
+    // "3d6 >= 15", "pairs 5" or "pi"
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim().to_lowercase().replace('≥', ">=").replace('≤', "<=");
+        if input == "pi" || input == "π" {
+            return Ok(Experiment::Pi);
+        }
+        if let Some(hand) = input.strip_prefix("pairs") {
+            let hand = parse_or_default(hand, 5, 2..=52, "hand size")?;
+            return Ok(Experiment::Pairs { hand });
+        }
+
+        let position = input.find(['>', '<', '=']).ok_or("expected 3d6 >= 15, pairs 5 or pi")?;
+        let (dice, condition) = input.split_at(position);
+        let (comparison, target) = [
+            (">=", Comparison::AtLeast),
+            ("<=", Comparison::AtMost),
+            (">", Comparison::Above),
+            ("<", Comparison::Below),
+            ("=", Comparison::Exactly),
+        ]
+            .into_iter()
+            .find_map(|(symbol, comparison)| condition.strip_prefix(symbol).map(|target| (comparison, target)))
+            .ok_or("expected a comparison like >= 15")?;
+        let target = target.trim().parse::<u64>().map_err(|e| format!("target: {}", e))?;
+
+        let (count, sides) = dice.trim().split_once('d').ok_or("dice are written like 3d6")?;
+        let count = match count {
+            "" => 1,
+            value => value.parse::<u32>().map_err(|e| format!("dice count: {}", e))?,
+        };
+        let sides = sides.parse::<u32>().map_err(|e| format!("dice sides: {}", e))?;
+        if count == 0 || count > 1000 || sides < 2 {
+            return Err(String::from("roll between 1 and 1000 dice with at least 2 sides"));
+        }
+        Ok(Experiment::Dice { count, sides, comparison, target })
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Experiment::Dice { count, sides, comparison, target } => {
+                format!("P({}d{} {} {})", count, sides, comparison.symbol(), target)
+            }
+            Experiment::Pairs { hand } => format!("mean pairs in {} cards", hand),
+            Experiment::Pi => String::from("pi"),
+        }
+    }
+
+    pub fn trial(&self, rng: &mut ChaCha20Rng) -> f64 {
+        match self {
+            Experiment::Dice { count, sides, comparison, target } => {
+                // 1000 dice of up to u32::MAX sides don't fit in a u32 sum
+                let total: u64 = (0..*count).map(|_| u64::from(rng.random_range(1..=*sides))).sum();
+                if comparison.holds(total, *target) { 1.0 } else { 0.0 }
+            }
+            // partial Fisher-Yates for the hand, a pair is a rank held exactly twice
+            Experiment::Pairs { hand } => {
+                let mut deck: [u8; 52] = std::array::from_fn(|index| index as u8);
+                let mut ranks = [0u8; 13];
+                for index in 0..*hand {
+                    let swap = rng.random_range(index..52);
+                    deck.swap(index, swap);
+                    ranks[(deck[index] % 13) as usize] += 1;
+                }
+                ranks.iter().filter(|count| **count == 2).count() as f64
+            }
+            // area of the quarter circle inside the unit square is pi / 4
+            Experiment::Pi => {
+                let (x, y): (f64, f64) = (rng.random(), rng.random());
+                if x * x + y * y <= 1.0 { 4.0 } else { 0.0 }
+            }
+        }
+    }
+}
+
+impl Totals {
+    fn add(&mut self, other: &Totals) {
+        self.iterations += other.iterations;
+        self.sum += other.sum;
+        self.sum_squares += other.sum_squares;
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum / self.iterations as f64
+    }
+
+    pub fn standard_error(&self) -> f64 {
+        let n = self.iterations as f64;
+        let variance = (self.sum_squares - self.sum * self.sum / n) / (n - 1.0).max(1.0);
+        (variance.max(0.0) / n).sqrt()
+    }
+}
+
+// every worker gets the same key on its own ChaCha stream, so the streams never overlap
+fn worker(experiment: Experiment, mut rng: ChaCha20Rng, iterations: u64, done: &AtomicU64, cancel: &AtomicBool) -> Totals {
+    let mut totals = Totals::default();
+    while totals.iterations < iterations && !cancel.load(Ordering::Relaxed) {
+        let chunk = CHUNK_ITERATIONS.min(iterations - totals.iterations);
+        for _ in 0..chunk {
+            let value = experiment.trial(&mut rng);
+            totals.sum += value;
+            totals.sum_squares += value * value;
+        }
+        totals.iterations += chunk;
+        done.fetch_add(chunk, Ordering::Relaxed);
+    }
+    totals
+}
+
+fn simulate(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let experiment = Experiment::parse(&prompt(terminal, state, "Experiment: 3d6 >= 15, pairs 5, pi")?)?;
+    let input = prompt(terminal, state, &format!("Iterations (blank for {})", DEFAULT_ITERATIONS))?;
+    let iterations = parse_or_default(&input, DEFAULT_ITERATIONS, 2..=MAX_ITERATIONS, "iterations")?;
+
+    let threads = thread::available_parallelism().map(|count| count.get() as u64).unwrap_or(4).min(iterations);
+    let seed: [u8; 32] = rng.random();
+    let done = AtomicU64::new(0);
+    let cancel = AtomicBool::new(false);
+    let started = Instant::now();
+    let label = format!("{} on {} threads, Esc to cancel", experiment.describe(), threads);
+
+    let mut totals = Totals::default();
+    let joined = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|index| {
+                let mut stream = ChaCha20Rng::from_seed(seed);
+                stream.set_stream(index);
+                // the first workers take the remainder so the counts add up exactly
+                let share = iterations / threads + u64::from(index < iterations % threads);
+                let (done, cancel) = (&done, &cancel);
+                scope.spawn(move || worker(experiment, stream, share, done, cancel))
+            })
+            .collect();
+
+        while !handles.iter().all(|handle| handle.is_finished()) {
+            state.progress = Some((label.clone(), done.load(Ordering::Relaxed) as f64 / iterations as f64));
+            terminal.draw(|frame| draw(frame, state)).expect("failed to draw frame");
+            if event::poll(Duration::from_millis(UPDATE_RATE_MILLIS)).unwrap_or(false)
+                && let Ok(Event::Key(key)) = event::read()
+                && key.code == KeyCode::Esc {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        }
+        // join every worker before reporting, scope() itself panics on a panicked worker left unjoined
+        let mut panicked = false;
+        for handle in handles {
+            match handle.join() {
+                Ok(worker_totals) => totals.add(&worker_totals),
+                Err(_) => panicked = true,
+            }
+        }
+        if panicked { Err(String::from("a simulation worker panicked")) } else { Ok(()) }
+    });
+    state.progress = None;
+    joined?;
+
+    if totals.iterations < 2 {
+        return Err(String::from("cancelled before any results"));
+    }
+    let (mean, error) = (totals.mean(), totals.standard_error());
+    let cancelled = if cancel.load(Ordering::Relaxed) { " (cancelled)" } else { "" };
+    state.push_message_output(format!(
+        "{} ≈ {:.6} ± {:.6} SE, 95% CI [{:.6}, {:.6}]",
+        experiment.describe(), mean, error, mean - Z_95 * error, mean + Z_95 * error
+    ));
+    state.push_message_output(format!(
+        "{} iterations in {:.2}s{}", totals.iterations, started.elapsed().as_secs_f64(), cancelled
+    ));
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = simulate(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
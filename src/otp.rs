@@ -0,0 +1,203 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, KeyInit, Mac};
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+use sha1::Sha1;
+
+use crate::{encoding, parse_or_default, prompt, qr, tokens, State};
+
+// 160 bits, the key length RFC 4226 recommends for HMAC-SHA1
+const SECRET_BYTES: usize = 20;
+// a day, nothing real uses periods anywhere near that long
+const MAX_PERIOD: u64 = 86_400;
+
+#[derive(Clone, Copy)]
+pub enum OtpKind {
+    Totp { period: u64 },
+    Hotp { counter: u64 },
+}
+
+// the last generated secret, kept in State so its codes can be checked afterwards
+pub struct OtpConfig {
+    pub kind: OtpKind,
+    pub secret: Vec<u8>,
+    pub issuer: String,
+    pub account: String,
+    pub digits: u32,
+}
+
+// RFC 3986 unreserved characters pass through, everything else is %XX
+pub fn percent_encode(input: &str) -> String {
+    input.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+// RFC 4226 section 5.3, HMAC-SHA1 with dynamic truncation
+pub fn hotp(secret: &[u8], counter: u64, digits: u32) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([hash[offset] & 0x7f, hash[offset + 1], hash[offset + 2], hash[offset + 3]]);
+    format!("{:0width$}", binary % 10u32.pow(digits), width = digits as usize)
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+impl OtpConfig {
+    pub fn secret_base32(&self) -> String {
+        encoding::base32(&self.secret, encoding::BASE32_ALPHABET, false)
+    }
+
+    pub fn uri(&self) -> String {
+        let (kind, extra) = match self.kind {
+            OtpKind::Totp { period } => ("totp", format!("period={}", period)),
+            OtpKind::Hotp { counter } => ("hotp", format!("counter={}", counter)),
+        };
+        let label = if self.issuer.is_empty() {
+            percent_encode(&self.account)
+        } else {
+            format!("{}:{}", percent_encode(&self.issuer), percent_encode(&self.account))
+        };
+        let mut uri = format!(
+            "otpauth://{}/{}?secret={}&algorithm=SHA1&digits={}&{}",
+            kind, label, self.secret_base32(), self.digits, extra
+        );
+        if !self.issuer.is_empty() {
+            uri.push_str(&format!("&issuer={}", percent_encode(&self.issuer)));
+        }
+        uri
+    }
+
+    // the code an authenticator app shows right now (totp) or for the next counter (hotp)
+    pub fn current_code(&self) -> String {
+        match self.kind {
+            OtpKind::Totp { period } => hotp(&self.secret, unix_seconds() / period, self.digits),
+            OtpKind::Hotp { counter } => hotp(&self.secret, counter, self.digits),
+        }
+    }
+}
+
+fn new_secret(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let input = prompt(terminal, state, "Type (totp or hotp, blank for totp)")?;
+    let hotp = match input.trim().to_lowercase().as_str() {
+        "" | "totp" => false,
+        "hotp" => true,
+        other => return Err(format!("unknown otp type \"{}\"", other)),
+    };
+
+    let issuer = prompt(terminal, state, "Issuer (e.g. company or service)")?.trim().to_string();
+    let account = prompt(terminal, state, "Account name")?.trim().to_string();
+    if account.is_empty() {
+        return Err(String::from("account name cannot be empty"));
+    }
+    if issuer.contains(':') || account.contains(':') {
+        return Err(String::from("issuer and account cannot contain ':'"));
+    }
+
+    let digits = parse_or_default(&prompt(terminal, state, "Digits (6 or 8, blank for 6)")?, 6u32, 6..=8, "digits")?;
+    if digits == 7 {
+        return Err(String::from("digits must be 6 or 8"));
+    }
+    let kind = if hotp {
+        let counter = parse_or_default(&prompt(terminal, state, "Initial counter (blank for 0)")?, 0, 0..=u64::MAX, "counter")?;
+        OtpKind::Hotp { counter }
+    } else {
+        OtpKind::Totp { period: parse_or_default(&prompt(terminal, state, "Period in seconds (blank for 30)")?, 30, 1..=MAX_PERIOD, "period")? }
+    };
+
+    let config = OtpConfig { kind, secret: tokens::random_bytes(rng, SECRET_BYTES), issuer, account, digits };
+    let uri = config.uri();
+    let lines = qr::render(&uri)?;
+
+    state.push_message_output(format!("secret: {}", config.secret_base32()));
+    state.push_message_output(uri);
+    state.push_message_output(format!("current code: {}", config.current_code()));
+    // pushed last so the whole code sits at the bottom of the pane. too small to scan, the uri
+    // above can still be pasted into an authenticator
+    match qr::fits_output_pane(terminal, &lines) {
+        Ok(()) => state.push_output_lines(lines),
+        Err(error) => state.push_message_output(error),
+    }
+    state.otp_config = Some(config);
+    Ok(())
+}
+
+fn show_code(terminal: &mut DefaultTerminal, state: &mut State) -> Result<(), String> {
+    let input = prompt(terminal, state, "Base32 secret (blank for the last generated one)")?;
+    if input.trim().is_empty() {
+        let config = state.otp_config.as_ref().ok_or("no secret generated yet")?;
+        let message = match config.kind {
+            OtpKind::Totp { period } => format!(
+                "{}: {} ({}s left)", config.account, config.current_code(), period - unix_seconds() % period
+            ),
+            OtpKind::Hotp { counter } => format!("{}: {} (counter {})", config.account, config.current_code(), counter),
+        };
+        state.push_message_output(message);
+        return Ok(());
+    }
+
+    // pasted secrets are assumed to use the common defaults: totp, 6 digits, 30 seconds
+    let secret = encoding::decode_base32(&input)?;
+    if secret.is_empty() {
+        return Err(String::from("secret is empty"));
+    }
+    let code = hotp(&secret, unix_seconds() / 30, 6);
+    state.push_message_output(format!("code: {} ({}s left)", code, 30 - unix_seconds() % 30));
+    Ok(())
+}
+
+fn otp_action(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let input = prompt(terminal, state, "OTP: new | code (blank for new)")?;
+    match input.trim().to_lowercase().as_str() {
+        "" | "new" => new_secret(terminal, state, rng),
+        "code" => show_code(terminal, state),
+        other => Err(format!("unknown otp action \"{}\"", other)),
+    }
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = otp_action(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn hotp_matches_rfc_4226_appendix_d() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(hotp(SECRET, counter as u64, 6), *code, "counter {}", counter);
+        }
+    }
+
+    #[test]
+    fn totp_matches_rfc_6238_appendix_b() {
+        let expected = [
+            (59, "94287082"),
+            (1_111_111_109, "07081804"),
+            (1_111_111_111, "14050471"),
+            (1_234_567_890, "89005924"),
+            (2_000_000_000, "69279037"),
+            (20_000_000_000, "65353130"),
+        ];
+        for (seconds, code) in expected {
+            assert_eq!(hotp(SECRET, seconds / 30, 8), code, "time {}", seconds);
+        }
+    }
+}
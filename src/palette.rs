@@ -0,0 +1,267 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::{
+    style::{Color, Stylize},
+    text::Span,
+    DefaultTerminal,
+};
+
+use crate::{parse_or_default, prompt, prompt_count, State};
+
+const MAX_COUNT: usize = 16;
+const MAX_ATTEMPTS: u32 = 10_000;
+// WCAG 2 AA for normal text
+const DEFAULT_CONTRAST: f64 = 4.5;
+// black on white is 21:1 but random pairs that far apart are too rare for the sampler to find
+const MAX_CONTRAST: f64 = 12.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[derive(Clone, Copy)]
+pub enum Space {
+    Rgb,
+    Hsl,
+    Oklch,
+}
+
+pub enum Mode {
+    Random,
+    Analogous,
+    Complementary,
+    Triadic,
+    Contrast,
+}
+
+fn to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn from_linear(c: f64) -> u8 {
+    let c = if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// OKLab matrices from Björn Ottosson's reference implementation, working on linear sRGB
+fn oklch_to_linear(lightness: f64, chroma: f64, hue: f64) -> [f64; 3] {
+    let (a, b) = (chroma * hue.to_radians().cos(), chroma * hue.to_radians().sin());
+    let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+impl Rgb {
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    pub fn color(&self) -> Color {
+        Color::Rgb(self.r, self.g, self.b)
+    }
+
+    // hue in degrees, saturation and lightness in 0..=1
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let offset = lightness - chroma / 2.0;
+        let channel = |value: f64| ((value + offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Rgb { r: channel(r), g: channel(g), b: channel(b) }
+    }
+
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let (r, g, b) = (self.r as f64 / 255.0, self.g as f64 / 255.0, self.b as f64 / 255.0);
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (hue, saturation, lightness)
+    }
+
+    // out of gamut colours keep their lightness and hue, chroma is reduced until they fit
+    pub fn from_oklch(lightness: f64, chroma: f64, hue: f64) -> Self {
+        let in_gamut = |chroma: f64| oklch_to_linear(lightness, chroma, hue).iter().all(|c| (-1e-6..=1.0 + 1e-6).contains(c));
+        let mut fitted = chroma;
+        if !in_gamut(chroma) {
+            let (mut low, mut high) = (0.0, chroma);
+            for _ in 0..24 {
+                let middle = (low + high) / 2.0;
+                if in_gamut(middle) { low = middle } else { high = middle }
+            }
+            fitted = low;
+        }
+        let [r, g, b] = oklch_to_linear(lightness, fitted, hue);
+        Rgb { r: from_linear(r), g: from_linear(g), b: from_linear(b) }
+    }
+
+    pub fn to_oklch(self) -> (f64, f64, f64) {
+        let (r, g, b) = (to_linear(self.r), to_linear(self.g), to_linear(self.b));
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+        (lightness, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0))
+    }
+
+    // WCAG 2 relative luminance
+    pub fn luminance(&self) -> f64 {
+        0.2126 * to_linear(self.r) + 0.7152 * to_linear(self.g) + 0.0722 * to_linear(self.b)
+    }
+
+    pub fn describe(&self, space: Space) -> String {
+        match space {
+            Space::Rgb => format!("rgb({}, {}, {})", self.r, self.g, self.b),
+            Space::Hsl => {
+                let (hue, saturation, lightness) = self.to_hsl();
+                format!("hsl({:.0} {:.0}% {:.0}%)", hue, saturation * 100.0, lightness * 100.0)
+            }
+            Space::Oklch => {
+                let (lightness, chroma, hue) = self.to_oklch();
+                format!("oklch({:.1}% {:.3} {:.1})", lightness * 100.0, chroma, hue)
+            }
+        }
+    }
+}
+
+pub fn contrast_ratio(first: &Rgb, second: &Rgb) -> f64 {
+    let (a, b) = (first.luminance(), second.luminance());
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+impl Space {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "rgb" => Ok(Space::Rgb),
+            "hsl" => Ok(Space::Hsl),
+            "" | "oklch" => Ok(Space::Oklch),
+            other => Err(format!("unknown colour space \"{}\", expected rgb, hsl or oklch", other)),
+        }
+    }
+
+    // rgb is uniform over the cube, hsl and oklch stay away from washed out and near black colours
+    pub fn random(&self, rng: &mut ChaCha20Rng) -> Rgb {
+        match self {
+            Space::Rgb => {
+                let [r, g, b]: [u8; 3] = rng.random();
+                Rgb { r, g, b }
+            }
+            Space::Hsl => Rgb::from_hsl(rng.random_range(0.0..360.0), rng.random_range(0.35..1.0), rng.random_range(0.25..0.75)),
+            Space::Oklch => Rgb::from_oklch(rng.random_range(0.45..0.9), rng.random_range(0.06..0.2), rng.random_range(0.0..360.0)),
+        }
+    }
+}
+
+impl Mode {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "" | "random" => Ok(Mode::Random),
+            "analogous" => Ok(Mode::Analogous),
+            "complementary" => Ok(Mode::Complementary),
+            "triadic" => Ok(Mode::Triadic),
+            "contrast" | "pair" | "pairs" => Ok(Mode::Contrast),
+            other => Err(format!("unknown mode \"{}\"", other)),
+        }
+    }
+}
+
+// harmonies are rotated in OKLCH so the colours look equally light, unlike HSL rotations
+pub fn harmony(rng: &mut ChaCha20Rng, mode: &Mode) -> Vec<Rgb> {
+    let (lightness, chroma, hue) = (rng.random_range(0.55..0.8), rng.random_range(0.08..0.18), rng.random_range(0.0..360.0));
+    let at = |offset: f64, lightness: f64| Rgb::from_oklch(lightness, chroma, (hue + offset).rem_euclid(360.0));
+    match mode {
+        Mode::Analogous => [-60.0, -30.0, 0.0, 30.0, 60.0].iter().map(|offset| at(*offset, lightness)).collect(),
+        Mode::Complementary => vec![at(0.0, lightness), at(0.0, lightness - 0.2), at(180.0, lightness), at(180.0, lightness - 0.2)],
+        Mode::Triadic => [0.0, 120.0, 240.0].iter().map(|offset| at(*offset, lightness)).collect(),
+        Mode::Random | Mode::Contrast => vec![at(0.0, lightness)],
+    }
+}
+
+// rejection sampled from random rgb colours, so every pair above the ratio is equally likely
+pub fn contrast_pair(rng: &mut ChaCha20Rng, minimum: f64) -> Result<(Rgb, Rgb), String> {
+    for _ in 0..MAX_ATTEMPTS {
+        let foreground = Space::Rgb.random(rng);
+        let background = Space::Rgb.random(rng);
+        if contrast_ratio(&foreground, &background) >= minimum {
+            return Ok((foreground, background));
+        }
+    }
+    Err(format!("couldn't find a pair with contrast {:.1}:1", minimum))
+}
+
+fn swatch_spans(color: &Rgb, space: Space) -> Vec<Span<'static>> {
+    vec![
+        Span::raw("██████").fg(color.color()),
+        Span::raw(format!(" {}  {}", color.hex(), color.describe(space))),
+    ]
+}
+
+fn generate_colours(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let mode = Mode::parse(&prompt(terminal, state, "Mode: random, analogous, complementary, triadic, contrast")?)?;
+
+    if let Mode::Contrast = mode {
+        let input = prompt(terminal, state, "Minimum contrast ratio (blank for 4.5, AAA is 7)")?;
+        let minimum = parse_or_default(&input, DEFAULT_CONTRAST, 1.0..=MAX_CONTRAST, "minimum contrast")?;
+        let count = prompt_count(terminal, state, MAX_COUNT)?;
+        for _ in 0..count {
+            let (foreground, background) = contrast_pair(rng, minimum)?;
+            state.push_styled_output(vec![
+                Span::raw(" Sample text ").fg(foreground.color()).bg(background.color()),
+                Span::raw(format!(
+                    " {} on {}  {:.2}:1",
+                    foreground.hex(), background.hex(), contrast_ratio(&foreground, &background)
+                )),
+            ]);
+        }
+        return Ok(());
+    }
+
+    let space = Space::parse(&prompt(terminal, state, "Colour space: rgb, hsl, oklch (blank for oklch)")?)?;
+    if let Mode::Random = mode {
+        let count = prompt_count(terminal, state, MAX_COUNT)?;
+        for _ in 0..count {
+            let color = space.random(rng);
+            state.push_styled_output(swatch_spans(&color, space));
+        }
+        return Ok(());
+    }
+    for color in harmony(rng, &mode) {
+        state.push_styled_output(swatch_spans(&color, space));
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_colours(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
@@ -0,0 +1,222 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{parse_or_default, prompt, prompt_count, State};
+
+const MIN_LENGTH: usize = 4;
+const MAX_LENGTH: usize = 12;
+const MAX_COUNT: usize = 16;
+// exact counting walks every possible pin, above this we estimate by sampling instead
+const MAX_EXACT_LENGTH: usize = 6;
+const ENTROPY_SAMPLES: u32 = 200_000;
+const MAX_ATTEMPTS: u32 = 100_000;
+
+// most frequently chosen pins from public pin-frequency studies
+const COMMON_PINS: &[&str] = &[
+    "1234", "1111", "0000", "1212", "7777", "1004", "2000", "4444", "2222", "6969", "9999", "3333", "5555",
+    "6666", "1122", "1313", "8888", "4321", "2001", "1010", "2580", "0852", "1379", "1470", "2468", "1357",
+    "123456", "654321", "111111", "000000", "123123", "666666", "121212", "112233", "789456", "159753",
+    "987654", "123321", "696969", "147258", "159357", "12345678", "11111111", "87654321", "00000000",
+];
+
+#[derive(Clone, Copy)]
+pub struct Rules {
+    pub repeats: bool,
+    pub sequences: bool,
+    pub dates: bool,
+    pub common: bool,
+}
+
+impl Rules {
+    // "all", "none", or a comma separated list of repeat, sequence, date, common
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim().to_lowercase();
+        match input.as_str() {
+            "" | "all" => return Ok(Rules { repeats: true, sequences: true, dates: true, common: true }),
+            "none" => return Ok(Rules { repeats: false, sequences: false, dates: false, common: false }),
+            _ => {}
+        }
+        let mut rules = Rules { repeats: false, sequences: false, dates: false, common: false };
+        for rule in input.split(',').map(str::trim) {
+            match rule {
+                "repeat" | "repeats" => rules.repeats = true,
+                "sequence" | "sequences" => rules.sequences = true,
+                "date" | "dates" => rules.dates = true,
+                "common" => rules.common = true,
+                other => return Err(format!("unknown rule \"{}\"", other)),
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn accepts(&self, pin: &[u8]) -> bool {
+        !(self.repeats && is_repeated(pin)
+            || self.sequences && is_sequence(pin)
+            || self.dates && is_date(pin)
+            || self.common && is_common(pin))
+    }
+}
+
+// all one digit, or a shorter block repeated (1212, 123123)
+fn is_repeated(pin: &[u8]) -> bool {
+    (1..=pin.len() / 2)
+        .filter(|block| pin.len().is_multiple_of(*block))
+        .any(|block| pin.chunks(block).all(|chunk| chunk == &pin[..block]))
+}
+
+// straight runs up or down, 1234 or 9876
+fn is_sequence(pin: &[u8]) -> bool {
+    let steps: Vec<i8> = pin.windows(2).map(|pair| pair[1] as i8 - pair[0] as i8).collect();
+    steps.iter().all(|&step| step == 1) || steps.iter().all(|&step| step == -1)
+}
+
+fn number(digits: &[u8]) -> u32 {
+    digits.iter().fold(0, |value, &digit| value * 10 + digit as u32)
+}
+
+fn is_day_month(day: u32, month: u32) -> bool {
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+fn is_year(year: u32) -> bool {
+    (1900..=2099).contains(&year)
+}
+
+// birthdays and years: DDMM/MMDD/YYYY, DDMMYY/MMDDYY/YYMMDD, DDMMYYYY/MMDDYYYY/YYYYMMDD
+fn is_date(pin: &[u8]) -> bool {
+    match pin.len() {
+        4 => {
+            let (first, second) = (number(&pin[..2]), number(&pin[2..]));
+            is_day_month(first, second) || is_day_month(second, first) || is_year(number(pin))
+        }
+        6 => {
+            let (a, b, c) = (number(&pin[..2]), number(&pin[2..4]), number(&pin[4..]));
+            is_day_month(a, b) || is_day_month(b, a) || is_day_month(c, b)
+        }
+        8 => {
+            let (a, b) = (number(&pin[..2]), number(&pin[2..4]));
+            let (c, d) = (number(&pin[4..6]), number(&pin[6..]));
+            (is_year(number(&pin[4..])) && (is_day_month(a, b) || is_day_month(b, a)))
+                || (is_year(number(&pin[..4])) && is_day_month(d, c))
+        }
+        _ => false,
+    }
+}
+
+fn is_common(pin: &[u8]) -> bool {
+    COMMON_PINS.iter().any(|common| common.bytes().map(|byte| byte - b'0').eq(pin.iter().copied()))
+}
+
+fn to_string(pin: &[u8]) -> String {
+    pin.iter().map(|digit| (b'0' + digit) as char).collect()
+}
+
+pub fn generate(rng: &mut ChaCha20Rng, length: usize, rules: Rules) -> Result<String, String> {
+    // rejection sampling keeps the accepted pins uniformly distributed
+    for _ in 0..MAX_ATTEMPTS {
+        let pin: Vec<u8> = (0..length).map(|_| rng.random_range(0..10)).collect();
+        if rules.accepts(&pin) {
+            return Ok(to_string(&pin));
+        }
+    }
+    Err(String::from("rules rejected every pin that was tried"))
+}
+
+// returns (bits, exact) where bits is log2 of the number of pins the rules still allow
+pub fn effective_entropy(rng: &mut ChaCha20Rng, length: usize, rules: Rules) -> (f64, bool) {
+    let naive_bits = length as f64 * 10f64.log2();
+    if length <= MAX_EXACT_LENGTH {
+        let total = 10u32.pow(length as u32);
+        let mut pin = vec![0u8; length];
+        let accepted = (0..total)
+            .filter(|value| {
+                let mut value = *value;
+                for digit in pin.iter_mut().rev() {
+                    *digit = (value % 10) as u8;
+                    value /= 10;
+                }
+                rules.accepts(&pin)
+            })
+            .count();
+        ((accepted as f64).log2(), true)
+    } else {
+        let accepted = (0..ENTROPY_SAMPLES)
+            .filter(|_| {
+                let pin: Vec<u8> = (0..length).map(|_| rng.random_range(0..10)).collect();
+                rules.accepts(&pin)
+            })
+            .count();
+        (naive_bits + (accepted as f64 / ENTROPY_SAMPLES as f64).log2(), false)
+    }
+}
+
+fn generate_pins(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let length = parse_or_default(&prompt(terminal, state, "PIN length (blank for 4)")?, 4, MIN_LENGTH..=MAX_LENGTH, "length")?;
+
+    let input = prompt(terminal, state, "Reject: all, none, or repeat,sequence,date,common (blank for all)")?;
+    let rules = Rules::parse(&input)?;
+
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for _ in 0..count {
+        let pin = generate(rng, length, rules)?;
+        state.push_message_output(format!("PIN: {}", pin));
+    }
+
+    let naive_bits = length as f64 * 10f64.log2();
+    let (bits, exact) = effective_entropy(rng, length, rules);
+    state.push_message_output(format!(
+        "entropy: {:.2} bits{} (vs {:.2} bits unfiltered)",
+        bits, if exact { "" } else { " estimated" }, naive_bits
+    ));
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_pins(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(pin: &str) -> Vec<u8> {
+        pin.bytes().map(|byte| byte - b'0').collect()
+    }
+
+    #[test]
+    fn detects_weak_patterns() {
+        // pin, repeated, sequence, date
+        let cases = [
+            ("1212", true, false, true),
+            ("123123", true, false, true),
+            ("9876", false, true, false),
+            ("0101", true, false, true),
+            ("311299", false, false, true),
+            ("19991231", false, false, true),
+            ("5832", false, false, false),
+            ("584937", false, false, false),
+        ];
+        for (pin, repeated, sequence, date) in cases {
+            let pin_digits = digits(pin);
+            assert_eq!(is_repeated(&pin_digits), repeated, "{} repeated", pin);
+            assert_eq!(is_sequence(&pin_digits), sequence, "{} sequence", pin);
+            assert_eq!(is_date(&pin_digits), date, "{} date", pin);
+        }
+    }
+
+    #[test]
+    fn accepts_only_pins_that_pass_every_rule() {
+        let all = Rules::parse("all").unwrap();
+        assert!(all.accepts(&digits("5832")));
+        assert!(all.accepts(&digits("584937")));
+        for weak in ["1212", "123123", "9876", "0101", "311299", "19991231", "2580"] {
+            assert!(!all.accepts(&digits(weak)), "{}", weak);
+        }
+        let none = Rules::parse("none").unwrap();
+        assert!(none.accepts(&digits("1111")));
+    }
+}
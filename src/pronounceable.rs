@@ -0,0 +1,84 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{parse_or_default, prompt, shamir, State};
+
+// c, q and x left out since they are said like other consonants and make spelling ambiguous
+const CONSONANTS: &[u8] = b"bdfghjklmnprstvwz";
+const VOWELS: &[u8] = b"aeiou";
+const DIGITS: &[u8] = b"0123456789";
+// same set the memorable password generator uses as separators
+const SYMBOLS: &[u8] = b"!@#$%&*=./-";
+const MIN_LENGTH: usize = 4;
+const MAX_LENGTH: usize = 64;
+const MAX_EXTRAS: usize = 16;
+
+fn pick(rng: &mut ChaCha20Rng, alphabet: &[u8]) -> char {
+    alphabet[rng.random_range(0..alphabet.len())] as char
+}
+
+fn log2_binomial(n: usize, k: usize) -> f64 {
+    (0..k).map(|index| ((n - index) as f64 / (index + 1) as f64).log2()).sum()
+}
+
+// consonant-vowel alternation (baku-tefi-...), then digits and symbols dropped in at random positions
+pub fn generate(rng: &mut ChaCha20Rng, length: usize, digits: usize, symbols: usize) -> String {
+    let mut password: Vec<char> = (0..length)
+        .map(|index| if index % 2 == 0 { pick(rng, CONSONANTS) } else { pick(rng, VOWELS) })
+        .collect();
+    for _ in 0..digits {
+        let position = rng.random_range(0..=password.len());
+        password.insert(position, pick(rng, DIGITS));
+    }
+    for _ in 0..symbols {
+        let position = rng.random_range(0..=password.len());
+        password.insert(position, pick(rng, SYMBOLS));
+    }
+    password.into_iter().collect()
+}
+
+// every distinct output counted once: the letters are a fixed C/V pattern, and since digits and
+// symbols never look like letters each placement of them is its own password. the insertion
+// order above isn't uniform over placements, which can only make this a slight overestimate
+pub fn entropy_bits(length: usize, digits: usize, symbols: usize) -> f64 {
+    let consonants = length.div_ceil(2) as f64;
+    let vowels = (length / 2) as f64;
+    let letters = consonants * (CONSONANTS.len() as f64).log2() + vowels * (VOWELS.len() as f64).log2();
+
+    let extras = digits + symbols;
+    let placements = log2_binomial(length + extras, extras) + log2_binomial(extras, digits);
+    letters + placements + digits as f64 * (DIGITS.len() as f64).log2() + symbols as f64 * (SYMBOLS.len() as f64).log2()
+}
+
+// what a strength meter would claim: every character drawn from the full printable-ish alphabet
+pub fn naive_entropy_bits(length: usize, digits: usize, symbols: usize) -> f64 {
+    let mut alphabet = 26;
+    if digits > 0 {
+        alphabet += DIGITS.len();
+    }
+    if symbols > 0 {
+        alphabet += SYMBOLS.len();
+    }
+    (length + digits + symbols) as f64 * (alphabet as f64).log2()
+}
+
+fn generate_password(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let length = parse_or_default(&prompt(terminal, state, "Letters (blank for 12)")?, 12, MIN_LENGTH..=MAX_LENGTH, "letters")?;
+    let digits = parse_or_default(&prompt(terminal, state, "Digits (blank for 2)")?, 2, 0..=MAX_EXTRAS, "digits")?;
+    let symbols = parse_or_default(&prompt(terminal, state, "Symbols (blank for 1)")?, 1, 0..=MAX_EXTRAS, "symbols")?;
+
+    let password = generate(rng, length, digits, symbols);
+    state.push_message_output(format!("password: {}", password));
+    state.push_message_output(format!(
+        "entropy: {:.1} bits (a naive estimate would claim {:.1})",
+        entropy_bits(length, digits, symbols), naive_entropy_bits(length, digits, symbols)
+    ));
+    shamir::offer_split(terminal, state, rng, password.as_bytes())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_password(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
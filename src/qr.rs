@@ -0,0 +1,57 @@
+use qrcode::{EcLevel, QrCode};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    DefaultTerminal,
+};
+
+use crate::output_pane_size;
+
+// the spec asks for 4 modules, 2 is enough for phone scanners and saves precious pane width
+const QUIET_ZONE: usize = 2;
+
+// each terminal row holds two module rows: '▀' with the top module as foreground and the
+// bottom one as background. colours are forced to black on white so dark terminal themes
+// don't end up with an inverted code that some scanners refuse
+pub fn render(data: &str) -> Result<Vec<Line<'static>>, String> {
+    let code = QrCode::with_error_correction_level(data, EcLevel::M).map_err(|e| format!("QR code: {}", e))?;
+    let width = code.width();
+    let modules = code.to_colors();
+    let size = width + QUIET_ZONE * 2;
+
+    let is_dark = |row: usize, column: usize| -> bool {
+        if row < QUIET_ZONE || column < QUIET_ZONE || row >= width + QUIET_ZONE || column >= width + QUIET_ZONE {
+            return false;
+        }
+        modules[(row - QUIET_ZONE) * width + (column - QUIET_ZONE)] == qrcode::Color::Dark
+    };
+    let color = |dark: bool| if dark { Color::Black } else { Color::White };
+
+    let lines = (0..size).step_by(2)
+        .map(|row| {
+            let spans: Vec<Span<'static>> = (0..size)
+                .map(|column| {
+                    let style = Style::new().fg(color(is_dark(row, column))).bg(color(is_dark(row + 1, column)));
+                    Span::styled("▀", style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    Ok(lines)
+}
+
+// a code clipped by the output pane won't scan, in either direction
+pub fn fits_output_pane(terminal: &DefaultTerminal, lines: &[Line]) -> Result<(), String> {
+    let Some((pane_width, pane_height)) = output_pane_size(terminal) else {
+        return Ok(());
+    };
+    let width = lines.first().map(Line::width).unwrap_or(0);
+    if width > pane_width {
+        return Err(format!("QR code is {} columns wide, widen the terminal to at least {} to scan it", width, width * 2 + 6));
+    }
+    if lines.len() > pane_height {
+        return Err(format!("QR code is {} rows tall, make the terminal at least {} rows to scan it", lines.len(), lines.len() + 4));
+    }
+    Ok(())
+}
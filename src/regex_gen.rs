@@ -0,0 +1,510 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{prompt, prompt_count, State};
+
+const MAX_COUNT: usize = 16;
+const MAX_REPEAT: u32 = 1000;
+// longest string a whole pattern may produce, nested repeats multiply so MAX_REPEAT alone isn't enough
+const MAX_LENGTH: u64 = 10_000;
+// \s and . are limited to printable ASCII so generated test data stays readable
+const DOT_CLASS: (u8, u8) = (0x20, 0x7e);
+
+// supported subset: literals, escapes, . [classes] [^negated] \d \w \s (and upper-case
+// negations), groups, alternation, ? * + {n} {n,} {n,m}; ^ and $ are accepted and ignored
+enum Node {
+    Literal(char),
+    Class(Vec<char>), // already expanded so sampling is a single index
+    Group(Vec<Vec<Node>>), // alternatives, each a sequence
+    Repeat { node: Box<Node>, min: u32, max: u32 },
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    // applied to *, + and {n,} which have no upper bound of their own
+    cap: Option<u32>,
+}
+
+fn printable_ascii() -> impl Iterator<Item = char> {
+    (DOT_CLASS.0..=DOT_CLASS.1).map(char::from)
+}
+
+fn digit_class() -> Vec<char> {
+    ('0'..='9').collect()
+}
+
+fn word_class() -> Vec<char> {
+    ('a'..='z').chain('A'..='Z').chain('0'..='9').chain(['_']).collect()
+}
+
+fn space_class() -> Vec<char> {
+    vec![' ', '\t']
+}
+
+fn negate(class: &[char]) -> Vec<char> {
+    printable_ascii().filter(|character| !class.contains(character)).collect()
+}
+
+impl Parser {
+    // positions in messages are 1-based and point at the character the error is about
+    fn error_at(&self, index: usize, message: &str) -> String {
+        format!("{} at position {}", message, index + 1)
+    }
+
+    fn error(&self, message: &str) -> String {
+        self.error_at(self.position, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek();
+        self.position += 1;
+        character
+    }
+
+    fn parse_alternation(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.parse_sequence()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            alternatives.push(self.parse_sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn parse_sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut sequence = Vec::new();
+        while let Some(character) = self.peek() {
+            if character == '|' || character == ')' {
+                break;
+            }
+            let atom = match self.parse_atom()? {
+                Some(atom) => atom,
+                None => continue,
+            };
+            sequence.push(self.parse_quantifier(atom)?);
+        }
+        Ok(sequence)
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, String> {
+        let backslash = self.position - 1;
+        match self.next() {
+            Some('d') => Ok(Node::Class(digit_class())),
+            Some('D') => Ok(Node::Class(negate(&digit_class()))),
+            Some('w') => Ok(Node::Class(word_class())),
+            Some('W') => Ok(Node::Class(negate(&word_class()))),
+            Some('s') => Ok(Node::Class(space_class())),
+            Some('S') => Ok(Node::Class(negate(&space_class()))),
+            Some('t') => Ok(Node::Literal('\t')),
+            Some('n') => Ok(Node::Literal('\n')),
+            Some(character) if !character.is_ascii_alphanumeric() => Ok(Node::Literal(character)),
+            Some(character) => Err(self.error_at(backslash, &format!("unsupported escape \\{}", character))),
+            None => Err(self.error_at(backslash, "pattern ends with a lone backslash")),
+        }
+    }
+
+    // returns None for anchors, which don't produce output
+    fn parse_atom(&mut self) -> Result<Option<Node>, String> {
+        let start = self.position;
+        let character = self.next().ok_or_else(|| self.error_at(start, "unexpected end of pattern"))?;
+        let node = match character {
+            '^' | '$' => return Ok(None),
+            '.' => Node::Class(printable_ascii().collect()),
+            '\\' => self.parse_escape()?,
+            '[' => self.parse_class()?,
+            '(' => {
+                // non-capturing groups generate the same strings
+                if self.chars[self.position..].starts_with(&['?', ':']) {
+                    self.position += 2;
+                }
+                let alternatives = self.parse_alternation()?;
+                if self.next() != Some(')') {
+                    return Err(self.error_at(start, "missing ')'"));
+                }
+                Node::Group(alternatives)
+            }
+            '*' | '+' | '?' | '{' => return Err(self.error_at(start, &format!("'{}' has nothing to repeat", character))),
+            _ => Node::Literal(character),
+        };
+        Ok(Some(node))
+    }
+
+    fn parse_class_char(&mut self) -> Result<Vec<char>, String> {
+        match self.next() {
+            Some('\\') => match self.parse_escape()? {
+                Node::Literal(character) => Ok(vec![character]),
+                Node::Class(class) => Ok(class),
+                _ => unreachable!("escapes only produce literals or classes"),
+            },
+            Some(character) => Ok(vec![character]),
+            None => Err(self.error("missing ']'")),
+        }
+    }
+
+    fn parse_class(&mut self) -> Result<Node, String> {
+        let open = self.position - 1;
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.position += 1;
+        }
+        let mut class = Vec::new();
+        let mut first = true;
+        loop {
+            match self.peek() {
+                Some(']') if !first => {
+                    self.position += 1;
+                    break;
+                }
+                None => return Err(self.error_at(open, "missing ']'")),
+                _ => {}
+            }
+            first = false;
+            let range_start = self.position;
+            let start = self.parse_class_char()?;
+            if start.len() == 1 && self.peek() == Some('-') && self.chars.get(self.position + 1).is_some_and(|&next| next != ']') {
+                self.position += 1;
+                let end = self.parse_class_char()?;
+                if end.len() != 1 || end[0] < start[0] {
+                    return Err(self.error_at(range_start, "invalid range in character class"));
+                }
+                class.extend(start[0]..=end[0]);
+            } else {
+                class.extend(start);
+            }
+        }
+
+        class.sort_unstable();
+        class.dedup();
+        if negated {
+            class = negate(&class);
+        }
+        if class.is_empty() {
+            return Err(self.error_at(open, "character class matches nothing"));
+        }
+        Ok(Node::Class(class))
+    }
+
+    fn parse_number(&mut self) -> Option<u32> {
+        let start = self.position;
+        while self.peek().is_some_and(|character| character.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect::<String>().parse().ok()
+    }
+
+    fn unbounded(&self, start: usize, min: u32) -> Result<u32, String> {
+        match self.cap {
+            Some(cap) => Ok(cap.max(min)),
+            None => Err(self.error_at(start, "unbounded repeat, give a cap or use {n,m}")),
+        }
+    }
+
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, String> {
+        let start = self.position;
+        let (min, max) = match self.peek() {
+            Some('?') => {
+                self.position += 1;
+                (0, 1)
+            }
+            Some('*') => {
+                self.position += 1;
+                (0, self.unbounded(start, 0)?)
+            }
+            Some('+') => {
+                self.position += 1;
+                (1, self.unbounded(start, 1)?)
+            }
+            Some('{') => {
+                self.position += 1;
+                let min = self.parse_number().ok_or_else(|| self.error("expected a number after '{'"))?;
+                let max = if self.peek() == Some(',') {
+                    self.position += 1;
+                    match self.parse_number() {
+                        Some(max) => max,
+                        None => self.unbounded(start, min)?,
+                    }
+                } else {
+                    min
+                };
+                if self.peek() != Some('}') {
+                    return Err(self.error("missing '}'"));
+                }
+                self.position += 1;
+                if min > max {
+                    return Err(self.error_at(start, "repeat minimum is above the maximum"));
+                }
+                (min, max)
+            }
+            _ => return Ok(atom),
+        };
+        if max > MAX_REPEAT {
+            return Err(self.error_at(start, &format!("repeats are limited to {}", MAX_REPEAT)));
+        }
+        // lazy/possessive suffixes don't change what can match
+        if matches!(self.peek(), Some('?') | Some('+')) {
+            self.position += 1;
+        }
+        Ok(Node::Repeat { node: Box::new(atom), min, max })
+    }
+}
+
+pub struct Pattern {
+    root: Vec<Vec<Node>>,
+}
+
+impl Pattern {
+    pub fn parse(pattern: &str, cap: Option<u32>) -> Result<Self, String> {
+        let mut parser = Parser { chars: pattern.chars().collect(), position: 0, cap };
+        let root = parser.parse_alternation()?;
+        if parser.position < parser.chars.len() {
+            return Err(parser.error("unmatched ')'"));
+        }
+        if alternatives_max_length(&root) > MAX_LENGTH {
+            return Err(format!("pattern can produce strings over {} characters, lower the repeat counts", MAX_LENGTH));
+        }
+        Ok(Self { root })
+    }
+
+    // every choice is weighted by how many strings it leads to, so unambiguous patterns
+    // give each matching string the same probability
+    pub fn generate(&self, rng: &mut ChaCha20Rng) -> String {
+        let mut output = String::new();
+        generate_alternatives(&self.root, rng, &mut output);
+        output
+    }
+
+    // log10 of how many strings the pattern can produce
+    pub fn log10_matches(&self) -> f64 {
+        alternatives_log_count(&self.root) / std::f64::consts::LN_10
+    }
+}
+
+// saturating so {1000} inside {1000} inside {1000} still compares against MAX_LENGTH
+fn alternatives_max_length(alternatives: &[Vec<Node>]) -> u64 {
+    alternatives.iter()
+        .map(|sequence| sequence.iter().fold(0u64, |total, node| total.saturating_add(node_max_length(node))))
+        .max()
+        .unwrap_or(0)
+}
+
+fn node_max_length(node: &Node) -> u64 {
+    match node {
+        Node::Literal(_) | Node::Class(_) => 1,
+        Node::Group(alternatives) => alternatives_max_length(alternatives),
+        Node::Repeat { node, max, .. } => node_max_length(node).saturating_mul(u64::from(*max)),
+    }
+}
+
+// counts are kept as natural logs since {0,1000} of a class overflows f64 many times over
+fn log_sum_exp(values: &[f64]) -> f64 {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max == f64::NEG_INFINITY {
+        return max;
+    }
+    max + values.iter().map(|value| (value - max).exp()).sum::<f64>().ln()
+}
+
+fn sequence_log_count(sequence: &[Node]) -> f64 {
+    sequence.iter().map(node_log_count).sum()
+}
+
+fn alternatives_log_count(alternatives: &[Vec<Node>]) -> f64 {
+    let counts: Vec<f64> = alternatives.iter().map(|sequence| sequence_log_count(sequence)).collect();
+    log_sum_exp(&counts)
+}
+
+fn repeat_log_counts(node: &Node, min: u32, max: u32) -> Vec<f64> {
+    let single = node_log_count(node);
+    (min..=max).map(|times| times as f64 * single).collect()
+}
+
+fn node_log_count(node: &Node) -> f64 {
+    match node {
+        Node::Literal(_) => 0.0,
+        Node::Class(class) => (class.len() as f64).ln(),
+        Node::Group(alternatives) => alternatives_log_count(alternatives),
+        Node::Repeat { node, min, max } => log_sum_exp(&repeat_log_counts(node, *min, *max)),
+    }
+}
+
+// picks an index with probability proportional to exp(log_weights[index])
+fn pick_weighted(log_weights: &[f64], rng: &mut ChaCha20Rng) -> usize {
+    let total = log_sum_exp(log_weights);
+    let mut target = rng.random::<f64>();
+    for (index, weight) in log_weights.iter().enumerate() {
+        target -= (weight - total).exp();
+        if target < 0.0 {
+            return index;
+        }
+    }
+    log_weights.len() - 1
+}
+
+fn generate_alternatives(alternatives: &[Vec<Node>], rng: &mut ChaCha20Rng, output: &mut String) {
+    let counts: Vec<f64> = alternatives.iter().map(|sequence| sequence_log_count(sequence)).collect();
+    for node in &alternatives[pick_weighted(&counts, rng)] {
+        generate_node(node, rng, output);
+    }
+}
+
+fn generate_node(node: &Node, rng: &mut ChaCha20Rng, output: &mut String) {
+    match node {
+        Node::Literal(character) => output.push(*character),
+        Node::Class(class) => output.push(class[rng.random_range(0..class.len())]),
+        Node::Group(alternatives) => generate_alternatives(alternatives, rng, output),
+        Node::Repeat { node, min, max } => {
+            let times = *min + pick_weighted(&repeat_log_counts(node, *min, *max), rng) as u32;
+            for _ in 0..times {
+                generate_node(node, rng, output);
+            }
+        }
+    }
+}
+
+fn generate_matches(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let pattern = prompt(terminal, state, "Regex (e.g. [A-Z]{3}-\\d{4})")?;
+    let input = prompt(terminal, state, "Cap for * + {n,} (blank to reject them)")?;
+    let cap = match input.trim() {
+        "" => None,
+        value => Some(value.parse::<u32>().map_err(|e| e.to_string())?),
+    };
+    let pattern = Pattern::parse(&pattern, cap)?;
+
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for _ in 0..count {
+        let value = pattern.generate(rng);
+        state.push_message_output(value);
+    }
+    state.push_message_output(format!("pattern matches about 10^{:.1} strings", pattern.log10_matches()));
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_matches(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn samples(pattern: &str, cap: Option<u32>) -> Vec<String> {
+        let pattern = Pattern::parse(pattern, cap).unwrap();
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        (0..200).map(|_| pattern.generate(&mut rng)).collect()
+    }
+
+    fn parse_error(pattern: &str, cap: Option<u32>) -> String {
+        match Pattern::parse(pattern, cap) {
+            Ok(_) => panic!("{} should not parse", pattern),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn samples_match_the_pattern() {
+        for sample in samples(r"[A-Z]{3}-\d{4}", None) {
+            let chars: Vec<char> = sample.chars().collect();
+            assert_eq!(chars.len(), 8, "{}", sample);
+            assert!(chars[..3].iter().all(char::is_ascii_uppercase), "{}", sample);
+            assert_eq!(chars[3], '-');
+            assert!(chars[4..].iter().all(char::is_ascii_digit), "{}", sample);
+        }
+        for sample in samples("^x{2,4}$", None) {
+            assert!((2..=4).contains(&sample.len()) && sample.chars().all(|c| c == 'x'), "{}", sample);
+        }
+        for sample in samples(r"\w+@\w+\.com", Some(5)) {
+            let (user, domain) = sample.split_once('@').unwrap();
+            let host = domain.strip_suffix(".com").unwrap();
+            for part in [user, host] {
+                assert!((1..=5).contains(&part.len()), "{}", sample);
+                assert!(part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'), "{}", sample);
+            }
+        }
+    }
+
+    #[test]
+    fn class_edge_cases() {
+        // a leading ']' and a '-' at either end are literals
+        let mut seen = samples("[]a]", None);
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen, ["]", "a"]);
+        for pattern in ["[a-]", "[-a]"] {
+            let mut seen = samples(pattern, None);
+            seen.sort();
+            seen.dedup();
+            assert_eq!(seen, ["-", "a"], "{}", pattern);
+        }
+        for sample in samples(r"[^\d\s]", None) {
+            let character = sample.chars().next().unwrap();
+            assert!(character.is_ascii_graphic() && !character.is_ascii_digit(), "{:?}", sample);
+        }
+    }
+
+    #[test]
+    fn alternation_and_groups() {
+        for sample in samples("cat|dog|bird", None) {
+            assert!(["cat", "dog", "bird"].contains(&sample.as_str()), "{}", sample);
+        }
+        for sample in samples("(?:ab|cd){1,3}", None) {
+            assert!((2..=6).contains(&sample.len()) && sample.len() % 2 == 0, "{}", sample);
+            assert!(sample.as_bytes().chunks(2).all(|pair| pair == b"ab" || pair == b"cd"), "{}", sample);
+        }
+        for sample in samples("a(b(c|d)?)e", None) {
+            assert!(["abe", "abce", "abde"].contains(&sample.as_str()), "{}", sample);
+        }
+    }
+
+    #[test]
+    fn unbounded_repeats_need_a_cap() {
+        assert_eq!(parse_error("ab*", None), "unbounded repeat, give a cap or use {n,m} at position 3");
+        assert_eq!(parse_error("a{2,}", None), "unbounded repeat, give a cap or use {n,m} at position 2");
+        for sample in samples("a{2,}", Some(4)) {
+            assert!((2..=4).contains(&sample.len()), "{}", sample);
+        }
+        // a cap below the minimum still allows the minimum
+        assert!(samples("a{6,}", Some(4)).iter().all(|sample| sample.len() == 6));
+    }
+
+    #[test]
+    fn counts_matching_strings() {
+        let log10 = |pattern: &str| Pattern::parse(pattern, None).unwrap().log10_matches();
+        assert!((log10(r"\d{4}") - 4.0).abs() < 1e-9);
+        assert!((log10("a|b") - 2f64.log10()).abs() < 1e-9);
+        // "", a, b, aa, ab, ba, bb
+        assert!((log10("[ab]{0,2}") - 7f64.log10()).abs() < 1e-9);
+        assert_eq!(log10("abc"), 0.0);
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_character() {
+        let cases = [
+            (r"ab\q", "unsupported escape \\q at position 3"),
+            (r"ab\", "pattern ends with a lone backslash at position 3"),
+            ("a(bc", "missing ')' at position 2"),
+            ("a)b", "unmatched ')' at position 2"),
+            ("*a", "'*' has nothing to repeat at position 1"),
+            ("a|+", "'+' has nothing to repeat at position 3"),
+            ("x[ab", "missing ']' at position 2"),
+            ("[az-a]", "invalid range in character class at position 3"),
+            ("[^ -~]", "character class matches nothing at position 1"),
+            ("a{x}", "expected a number after '{' at position 3"),
+            ("a{2x", "missing '}' at position 4"),
+            ("ab{3,2}", "repeat minimum is above the maximum at position 3"),
+            ("a{2000}", "repeats are limited to 1000 at position 2"),
+        ];
+        for (pattern, expected) in cases {
+            assert_eq!(parse_error(pattern, None), expected, "{}", pattern);
+        }
+        assert_eq!(parse_error("a+", Some(5000)), "repeats are limited to 1000 at position 2");
+    }
+}
@@ -0,0 +1,252 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{encoding, prompt, State};
+
+const MAX_SHARES: usize = 255; // x = 0 is the secret itself, so 255 points at most
+
+pub struct Share {
+    pub x: u8,
+    pub data: Vec<u8>,
+}
+
+// GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1, done bitwise instead of
+// with log tables so the timing doesn't depend on the secret
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+// a^254 == a^-1 in GF(2^8)
+fn gf_inverse(a: u8) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+    let mut exponent = 254;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+// plain CRC-32 (IEEE), only there to catch typos when shares are copied by hand
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & 0u32.wrapping_sub(crc & 1));
+        }
+    }
+    !crc
+}
+
+impl Share {
+    fn checksum(&self) -> u32 {
+        let mut bytes = vec![self.x];
+        bytes.extend(&self.data);
+        crc32(&bytes)
+    }
+
+    // "<x>-<hex data>-<crc32>"
+    pub fn encode(&self) -> String {
+        format!("{}-{}-{:08x}", self.x, encoding::hex(&self.data), self.checksum())
+    }
+
+    pub fn decode(input: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = input.trim().split('-').collect();
+        let [x, data, checksum] = parts[..] else {
+            return Err(String::from("share must look like <x>-<hex>-<checksum>"));
+        };
+        let x = x.parse::<u8>().map_err(|e| format!("share index: {}", e))?;
+        if x == 0 {
+            return Err(String::from("share index cannot be 0"));
+        }
+        if data.is_empty() || !data.len().is_multiple_of(2) {
+            return Err(String::from("share data must be an even number of hex digits"));
+        }
+        let data = (0..data.len()).step_by(2)
+            .map(|index| u8::from_str_radix(&data[index..index + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|e| format!("share data: {}", e))?;
+        let checksum = u32::from_str_radix(checksum, 16).map_err(|e| format!("share checksum: {}", e))?;
+
+        let share = Share { x, data };
+        if share.checksum() != checksum {
+            return Err(format!("share {} has a bad checksum, check for typos", share.x));
+        }
+        Ok(share)
+    }
+}
+
+// each secret byte gets its own random polynomial of degree threshold - 1 with the byte as constant term
+pub fn split(secret: &[u8], shares: usize, threshold: usize, rng: &mut ChaCha20Rng) -> Result<Vec<Share>, String> {
+    if secret.is_empty() {
+        return Err(String::from("nothing to split"));
+    }
+    if threshold < 2 || threshold > shares || shares > MAX_SHARES {
+        return Err(format!("need 2 <= threshold <= shares <= {}", MAX_SHARES));
+    }
+
+    let mut result: Vec<Share> = (1..=shares as u8).map(|x| Share { x, data: Vec::with_capacity(secret.len()) }).collect();
+    for &byte in secret {
+        let mut coefficients = vec![byte];
+        coefficients.extend((1..threshold).map(|_| rng.random::<u8>()));
+        for share in result.iter_mut() {
+            // horner's method, highest coefficient first
+            let y = coefficients.iter().rev().fold(0, |acc, &coefficient| gf_mul(acc, share.x) ^ coefficient);
+            share.data.push(y);
+        }
+    }
+    Ok(result)
+}
+
+// lagrange interpolation at x = 0, needs at least `threshold` shares to give the right answer
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, String> {
+    let first = shares.first().ok_or("no shares given")?;
+    if shares.iter().any(|share| share.data.len() != first.data.len()) {
+        return Err(String::from("shares have different lengths, they are not from the same secret"));
+    }
+    for (index, share) in shares.iter().enumerate() {
+        if shares[..index].iter().any(|other| other.x == share.x) {
+            return Err(format!("share {} was given twice", share.x));
+        }
+    }
+
+    let secret = (0..first.data.len())
+        .map(|position| {
+            shares.iter().fold(0, |secret, share| {
+                // basis polynomial at 0: product of x_j / (x_j - x_i), subtraction is xor in GF(2^8)
+                let basis = shares.iter()
+                    .filter(|other| other.x != share.x)
+                    .fold(1, |basis, other| gf_mul(basis, gf_mul(other.x, gf_inverse(other.x ^ share.x))));
+                secret ^ gf_mul(share.data[position], basis)
+            })
+        })
+        .collect();
+    Ok(secret)
+}
+
+// asked after a password or token is generated, blank input skips splitting
+pub fn offer_split(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng, secret: &[u8]) -> Result<(), String> {
+    let input = prompt(terminal, state, "Split into shares? Enter N K (e.g. 5 3), blank to skip")?;
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+    let numbers = input.split_whitespace()
+        .map(|value| value.parse::<usize>().map_err(|e| format!("{}: {}", value, e)))
+        .collect::<Result<Vec<usize>, String>>()?;
+    let [shares, threshold] = numbers[..] else {
+        return Err(String::from("enter the share count and the threshold, e.g. 5 3"));
+    };
+
+    let shares = split(secret, shares, threshold, rng)?;
+    state.push_message_output(format!("{} shares, any {} of them recover the secret:", shares.len(), threshold));
+    for share in shares {
+        state.push_message_output(share.encode());
+    }
+    Ok(())
+}
+
+fn recombine(terminal: &mut DefaultTerminal, state: &mut State) -> Result<(), String> {
+    let mut shares = Vec::new();
+    loop {
+        let input = prompt(terminal, state, &format!("Share {} (blank when done)", shares.len() + 1))?;
+        if input.trim().is_empty() {
+            break;
+        }
+        shares.push(Share::decode(&input)?);
+    }
+
+    let secret = combine(&shares)?;
+    // too few shares silently gives garbage, which usually isn't printable text
+    match String::from_utf8(secret) {
+        Ok(text) if !text.chars().any(char::is_control) => state.push_message_output(format!("secret: {}", text)),
+        Ok(text) => state.push_message_output(format!("secret (hex): {}", encoding::hex(text.as_bytes()))),
+        Err(error) => state.push_message_output(format!("secret (hex): {}", encoding::hex(error.as_bytes()))),
+    }
+    state.push_message_output(format!("recombined from {} shares, too few shares gives a wrong secret", shares.len()));
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State) {
+    if let Err(error) = recombine(terminal, state) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const SECRET: &[u8] = b"correct horse battery staple";
+
+    fn subset(shares: &[Share], mask: u32) -> Vec<Share> {
+        shares.iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << index) != 0)
+            .map(|(_, share)| Share { x: share.x, data: share.data.clone() })
+            .collect()
+    }
+
+    #[test]
+    fn multiply_matches_known_products() {
+        // FIPS 197 section 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        for a in 0..=255u8 {
+            assert_eq!(gf_mul(a, 0), 0);
+            assert_eq!(gf_mul(a, 1), a);
+            for b in 0..=255u8 {
+                assert_eq!(gf_mul(a, b), gf_mul(b, a));
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_of_every_nonzero_element() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inverse(a)), 1, "inverse of {:#04x}", a);
+        }
+    }
+
+    #[test]
+    fn every_threshold_subset_recombines() {
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let (count, threshold) = (5, 3);
+        let shares = split(SECRET, count, threshold, &mut rng).unwrap();
+        for mask in 1..1u32 << count {
+            let chosen = subset(&shares, mask);
+            let recovered = combine(&chosen).unwrap();
+            if chosen.len() >= threshold {
+                assert_eq!(recovered, SECRET, "shares {:05b}", mask);
+            } else {
+                assert_ne!(recovered, SECRET, "shares {:05b}", mask);
+            }
+        }
+    }
+
+    #[test]
+    fn shares_survive_encoding() {
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+        let shares = split(SECRET, 4, 2, &mut rng).unwrap();
+        let decoded: Vec<Share> = shares.iter().map(|share| Share::decode(&share.encode()).unwrap()).collect();
+        assert_eq!(combine(&decoded[2..]).unwrap(), SECRET);
+
+        let mut typo = shares[0].encode();
+        typo.replace_range(2..3, if &typo[2..3] == "0" { "1" } else { "0" });
+        assert!(Share::decode(&typo).is_err());
+    }
+}
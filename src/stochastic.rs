@@ -0,0 +1,225 @@
+use std::io::Write;
+
+use crossterm::event::{self, Event};
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::{
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
+    DefaultTerminal,
+};
+
+use crate::{cli, draw, parse_or_default, prompt, State};
+
+const DEFAULT_STEPS: usize = 500;
+const MAX_STEPS: usize = 100_000;
+
+pub enum Process {
+    Walk,
+    Walk2d,
+    Brownian,
+    Markov { matrix: Vec<Vec<f64>> },
+    Gbm { start: f64, drift: f64, volatility: f64 },
+}
+
+// a finished path, x/y pairs in order plus the axis names used for the chart and the csv header
+pub struct Plot {
+    pub title: String,
+    pub x_label: &'static str,
+    pub y_label: &'static str,
+    pub points: Vec<(f64, f64)>,
+}
+
+// Box-Muller
+fn standard_normal(rng: &mut ChaCha20Rng) -> f64 {
+    let (u, v): (f64, f64) = (1.0 - rng.random::<f64>(), rng.random());
+    (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+}
+
+// rows separated by ';', "0.9 0.1; 0.5 0.5"
+pub fn parse_matrix(input: &str) -> Result<Vec<Vec<f64>>, String> {
+    let matrix: Vec<Vec<f64>> = input.split(';')
+        .map(|row| row.split_whitespace().map(|value| value.parse::<f64>().map_err(|e| format!("\"{}\": {}", value, e))).collect())
+        .collect::<Result<_, _>>()?;
+    let size = matrix.len();
+    for (index, row) in matrix.iter().enumerate() {
+        if row.len() != size {
+            return Err(format!("row {} has {} entries, the matrix needs to be {}x{}", index, row.len(), size, size));
+        }
+        if row.iter().any(|value| !(0.0..=1.0).contains(value)) || (row.iter().sum::<f64>() - 1.0).abs() > 1e-6 {
+            return Err(format!("row {} must be probabilities adding up to 1", index));
+        }
+    }
+    if size < 2 {
+        return Err(String::from("a chain needs at least two states"));
+    }
+    Ok(matrix)
+}
+
+impl Process {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Process::Walk => "random walk",
+            Process::Walk2d => "2-D random walk",
+            Process::Brownian => "Brownian motion",
+            Process::Markov { .. } => "Markov chain",
+            Process::Gbm { .. } => "geometric Brownian motion",
+        }
+    }
+
+    // time runs from 0 to 1 for the continuous processes, so `steps` only sets the resolution
+    pub fn simulate(&self, rng: &mut ChaCha20Rng, steps: usize) -> Plot {
+        let dt = 1.0 / steps as f64;
+        let mut points = Vec::with_capacity(steps + 1);
+        let (x_label, y_label) = match self {
+            Process::Walk => {
+                let mut position = 0.0;
+                points.push((0.0, position));
+                for step in 1..=steps {
+                    position += if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+                    points.push((step as f64, position));
+                }
+                ("step", "position")
+            }
+            Process::Walk2d => {
+                let (mut x, mut y) = (0.0, 0.0);
+                points.push((x, y));
+                for _ in 0..steps {
+                    match rng.random_range(0..4) {
+                        0 => x += 1.0,
+                        1 => x -= 1.0,
+                        2 => y += 1.0,
+                        _ => y -= 1.0,
+                    }
+                    points.push((x, y));
+                }
+                ("x", "y")
+            }
+            Process::Brownian => {
+                let mut value = 0.0;
+                points.push((0.0, value));
+                for step in 1..=steps {
+                    value += dt.sqrt() * standard_normal(rng);
+                    points.push((step as f64 * dt, value));
+                }
+                ("t", "W(t)")
+            }
+            Process::Markov { matrix } => {
+                let mut current = 0;
+                points.push((0.0, 0.0));
+                for step in 1..=steps {
+                    let mut pick: f64 = rng.random();
+                    // rounding can leave a sliver after the last column, it goes to the last state
+                    current = matrix[current].iter()
+                        .position(|probability| {
+                            pick -= probability;
+                            pick < 0.0
+                        })
+                        .unwrap_or(matrix.len() - 1);
+                    points.push((step as f64, current as f64));
+                }
+                ("step", "state")
+            }
+            // S(t + dt) = S(t) * exp((mu - sigma^2 / 2) dt + sigma sqrt(dt) Z)
+            Process::Gbm { start, drift, volatility } => {
+                let mut price = *start;
+                points.push((0.0, price));
+                for step in 1..=steps {
+                    price *= ((drift - volatility * volatility / 2.0) * dt + volatility * dt.sqrt() * standard_normal(rng)).exp();
+                    points.push((step as f64 * dt, price));
+                }
+                ("t", "price")
+            }
+        };
+        Plot { title: format!("{}, {} steps", self.name(), steps), x_label, y_label, points }
+    }
+}
+
+fn bounds(values: impl Iterator<Item = f64>, margin: f64) -> [f64; 2] {
+    let (min, max) = values.fold((f64::MAX, f64::MIN), |(min, max), value| (min.min(value), max.max(value)));
+    // flat paths still need a visible range
+    let padding = if max > min { (max - min) * margin } else { 0.5 };
+    [min - padding, max + padding]
+}
+
+fn axis_labels([min, max]: [f64; 2]) -> Vec<String> {
+    vec![format!("{:.2}", min), format!("{:.2}", (min + max) / 2.0), format!("{:.2}", max)]
+}
+
+impl Plot {
+    pub fn chart(&self) -> Chart<'_> {
+        let x_bounds = bounds(self.points.iter().map(|(x, _)| *x), 0.0);
+        let y_bounds = bounds(self.points.iter().map(|(_, y)| *y), 0.05);
+        let dataset = Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::LightGreen))
+            .data(&self.points);
+        Chart::new(vec![dataset])
+            .block(Block::new().title(format!("{} (any key to close)", self.title)).borders(Borders::ALL))
+            .x_axis(Axis::default().title(self.x_label).bounds(x_bounds).labels(axis_labels(x_bounds)))
+            .y_axis(Axis::default().title(self.y_label).bounds(y_bounds).labels(axis_labels(y_bounds)))
+    }
+
+    pub fn write_csv(&self, output: &mut dyn Write) -> Result<(), String> {
+        let io_error = |e: std::io::Error| e.to_string();
+        writeln!(output, "{},{}", self.x_label, self.y_label).map_err(io_error)?;
+        for (x, y) in &self.points {
+            writeln!(output, "{},{}", x, y).map_err(io_error)?;
+        }
+        output.flush().map_err(io_error)
+    }
+}
+
+fn simulate_process(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let input = prompt(terminal, state, "Process: walk, walk2d, brownian, markov, gbm")?;
+    let process = match input.trim().to_lowercase().as_str() {
+        "" | "walk" => Process::Walk,
+        "walk2d" => Process::Walk2d,
+        "brownian" => Process::Brownian,
+        "markov" => {
+            let input = prompt(terminal, state, "Transition matrix, rows split by ; (e.g. 0.9 0.1; 0.5 0.5)")?;
+            Process::Markov { matrix: parse_matrix(&input)? }
+        }
+        "gbm" => {
+            let start = parse_or_default(&prompt(terminal, state, "Starting price (blank for 100)")?, 100.0, 0.01..=1e9, "price")?;
+            let drift = parse_or_default(&prompt(terminal, state, "Yearly drift mu (blank for 0.05)")?, 0.05, -10.0..=10.0, "drift")?;
+            let input = prompt(terminal, state, "Yearly volatility sigma (blank for 0.2)")?;
+            let volatility = parse_or_default(&input, 0.2, 0.0..=10.0, "volatility")?;
+            Process::Gbm { start, drift, volatility }
+        }
+        other => return Err(format!("unknown process \"{}\"", other)),
+    };
+
+    let input = prompt(terminal, state, &format!("Steps (blank for {})", DEFAULT_STEPS))?;
+    let steps = parse_or_default(&input, DEFAULT_STEPS, 1..=MAX_STEPS, "steps")?;
+    let plot = process.simulate(rng, steps);
+
+    let path = prompt(terminal, state, "CSV file for the samples (blank to skip)")?;
+    if !path.trim().is_empty() {
+        let mut output = cli::open_output(Some(path.trim()))?;
+        plot.write_csv(&mut *output)?;
+    }
+
+    let (_, last) = plot.points.last().copied().unwrap_or_default();
+    let saved = if path.trim().is_empty() { String::new() } else { format!(", saved to {}", path.trim()) };
+    state.push_message_output(format!("{}: ended at {} = {:.4}{}", plot.title, plot.y_label, last, saved));
+
+    // the chart takes over the output pane until a key is pressed
+    state.plot = Some(plot);
+    loop {
+        terminal.draw(|frame| draw(frame, state)).expect("failed to draw frame");
+        if let Event::Key(_) = event::read().expect("failed to read event") {
+            break;
+        }
+    }
+    state.plot = None;
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = simulate_process(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
@@ -0,0 +1,266 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{ids, prompt, prompt_count, words, State};
+
+const MAX_RUN: usize = 256;
+const MAX_COUNT: usize = 16;
+const SYNTAX_HELP: &str = "placeholders: {word[:n]} {digit[:n]} {hex[:n]} {HEX[:n]} {alpha[:n]} {upper[:n]} {lower[:n]} \
+                           {alnum[:n]} {symbol[:n]} {a|b|c} {min-max} {uuid}, {{ and }} for literal braces";
+
+const DIGITS: &str = "0123456789";
+const HEX_LOWER: &str = "0123456789abcdef";
+const HEX_UPPER: &str = "0123456789ABCDEF";
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALPHA: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALNUM: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const SYMBOLS: &str = "!@#$%&*=./-";
+
+pub enum Token {
+    Literal(String),
+    Run { alphabet: &'static str, length: usize },
+    Words(usize),
+    Choice(Vec<String>),
+    Range { min: u64, max: u64 },
+    Uuid,
+}
+
+impl Token {
+    pub fn fill(&self, rng: &mut ChaCha20Rng, output: &mut String) {
+        match self {
+            Token::Literal(text) => output.push_str(text),
+            Token::Run { alphabet, length } => {
+                let alphabet = alphabet.as_bytes();
+                output.extend((0..*length).map(|_| alphabet[rng.random_range(0..alphabet.len())] as char));
+            }
+            Token::Words(count) => {
+                for _ in 0..*count {
+                    output.push_str(words::wordlist[rng.random_range(0..words::wordlist.len())]);
+                }
+            }
+            Token::Choice(options) => output.push_str(&options[rng.random_range(0..options.len())]),
+            Token::Range { min, max } => output.push_str(&rng.random_range(*min..=*max).to_string()),
+            Token::Uuid => output.push_str(&ids::uuid_v4(rng)),
+        }
+    }
+
+    pub fn entropy_bits(&self) -> f64 {
+        match self {
+            Token::Literal(_) => 0.0,
+            Token::Run { alphabet, length } => *length as f64 * (alphabet.len() as f64).log2(),
+            Token::Words(count) => *count as f64 * (words::wordlist.len() as f64).log2(),
+            // a repeated option is just a heavier weight, so {a|a} is 0 bits and {a|a|b} under 1
+            Token::Choice(options) => {
+                let total = options.len() as f64;
+                let mut distinct: Vec<&String> = options.iter().collect();
+                distinct.sort_unstable();
+                distinct.dedup();
+                distinct.iter()
+                    .map(|option| {
+                        let chance = options.iter().filter(|other| other == option).count() as f64 / total;
+                        -chance * chance.log2()
+                    })
+                    .sum()
+            }
+            Token::Range { min, max } => ((max - min) as f64 + 1.0).log2(),
+            Token::Uuid => 122.0, // 128 bits minus version and variant
+        }
+    }
+}
+
+fn parse_placeholder(body: &str, column: usize) -> Result<Token, String> {
+    if body.contains('|') {
+        let options: Vec<String> = body.split('|').map(String::from).collect();
+        return Ok(Token::Choice(options));
+    }
+    if let Some((min, max)) = body.split_once('-')
+        && let (Ok(min), Ok(max)) = (min.trim().parse::<u64>(), max.trim().parse::<u64>())
+    {
+        if min > max {
+            return Err(format!("range {{{}}} at column {} has min above max", body, column));
+        }
+        return Ok(Token::Range { min, max });
+    }
+
+    let (name, length) = match body.split_once(':') {
+        Some((name, length)) => {
+            let length = length.trim().parse::<usize>()
+                .map_err(|e| format!("bad length in {{{}}} at column {}: {}", body, column, e))?;
+            if length == 0 || length > MAX_RUN {
+                return Err(format!("length in {{{}}} at column {} must be 1 to {}", body, column, MAX_RUN));
+            }
+            (name.trim(), length)
+        }
+        None => (body.trim(), 1),
+    };
+
+    let alphabet = match name {
+        "word" | "words" => return Ok(Token::Words(length)),
+        "uuid" => return Ok(Token::Uuid),
+        "digit" | "digits" => DIGITS,
+        "hex" => HEX_LOWER,
+        "HEX" => HEX_UPPER,
+        "alpha" => ALPHA,
+        "upper" => UPPER,
+        "lower" => LOWER,
+        "alnum" => ALNUM,
+        "symbol" | "symbols" => SYMBOLS,
+        _ => return Err(format!("unknown placeholder {{{}}} at column {}", body, column)),
+    };
+    Ok(Token::Run { alphabet, length })
+}
+
+pub fn parse(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((index, character)) = chars.next() {
+        let column = template[..index].chars().count() + 1;
+        match character {
+            '{' if chars.peek().map(|&(_, next)| next) == Some('{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek().map(|&(_, next)| next) == Some('}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut body = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, '{')) => return Err(format!("nested '{{' inside the placeholder opened at column {}", column)),
+                        Some((_, next)) => body.push(next),
+                        None => return Err(format!("unclosed '{{' at column {}", column)),
+                    }
+                }
+                if !literal.is_empty() {
+                    tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                tokens.push(parse_placeholder(&body, column)?);
+            }
+            '}' => return Err(format!("unmatched '}}' at column {}, use }}}} for a literal brace", column)),
+            _ => literal.push(character),
+        }
+    }
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    Ok(tokens)
+}
+
+pub fn fill(tokens: &[Token], rng: &mut ChaCha20Rng) -> String {
+    let mut output = String::new();
+    for token in tokens {
+        token.fill(rng, &mut output);
+    }
+    output
+}
+
+pub fn entropy_bits(tokens: &[Token]) -> f64 {
+    tokens.iter().map(Token::entropy_bits).sum()
+}
+
+fn generate_from_template(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let template = prompt(terminal, state, "Template (e.g. svc-{word}-{digit:4}-{hex:6}, ? for help)")?;
+    if template.trim() == "?" {
+        state.push_message_output(SYNTAX_HELP.to_string());
+        return Ok(());
+    }
+    let tokens = parse(&template)?;
+
+    let count = prompt_count(terminal, state, MAX_COUNT)?;
+
+    for _ in 0..count {
+        let value = fill(&tokens, rng);
+        state.push_message_output(value);
+    }
+    state.push_message_output(format!("entropy: {:.1} bits per value", entropy_bits(&tokens)));
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_from_template(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn bits(template: &str) -> f64 {
+        entropy_bits(&parse(template).unwrap())
+    }
+
+    #[test]
+    fn parses_placeholders() {
+        let tokens = parse("svc-{word}-{digit:4}{a|b}{10-20}{uuid}").unwrap();
+        assert!(matches!(&tokens[0], Token::Literal(text) if text == "svc-"));
+        assert!(matches!(tokens[1], Token::Words(1)));
+        assert!(matches!(&tokens[2], Token::Literal(text) if text == "-"));
+        assert!(matches!(tokens[3], Token::Run { alphabet: DIGITS, length: 4 }));
+        assert!(matches!(&tokens[4], Token::Choice(options) if options == &["a", "b"]));
+        assert!(matches!(tokens[5], Token::Range { min: 10, max: 20 }));
+        assert!(matches!(tokens[6], Token::Uuid));
+        assert_eq!(tokens.len(), 7);
+    }
+
+    #[test]
+    fn fills_placeholders() {
+        let tokens = parse("id-{HEX:6}-{1-6}-{red|green}").unwrap();
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        for _ in 0..100 {
+            let value = fill(&tokens, &mut rng);
+            let parts: Vec<&str> = value.split('-').collect();
+            assert_eq!(parts[0], "id");
+            assert!(parts[1].len() == 6 && parts[1].chars().all(|c| HEX_UPPER.contains(c)), "{}", value);
+            assert!((1..=6).contains(&parts[2].parse::<u32>().unwrap()), "{}", value);
+            assert!(["red", "green"].contains(&parts[3]), "{}", value);
+        }
+    }
+
+    #[test]
+    fn doubled_braces_are_literals() {
+        let tokens = parse("{{x}}-{{{digit}}}").unwrap();
+        let value = fill(&tokens, &mut ChaCha20Rng::seed_from_u64(0));
+        assert!(value.starts_with("{x}-{") && value.ends_with('}') && value.len() == 7, "{}", value);
+        assert_eq!(entropy_bits(&parse("{{}}").unwrap()), 0.0);
+    }
+
+    #[test]
+    fn choice_entropy_counts_distinct_options() {
+        assert_eq!(bits("{|}"), 0.0);
+        assert_eq!(bits("{a|a}"), 0.0);
+        assert_eq!(bits("{a|b|c|d}"), 2.0);
+        assert!((bits("{a|a|b}") - 0.9183).abs() < 1e-4);
+        assert_eq!(bits("{digit:3}{1-8}"), 3.0 * 10f64.log2() + 3.0);
+    }
+
+    #[test]
+    fn errors_report_the_column() {
+        let cases = [
+            ("ab{digit", "unclosed '{' at column 3"),
+            ("a}b", "unmatched '}' at column 2, use }} for a literal brace"),
+            ("x{a{b}}", "nested '{' inside the placeholder opened at column 2"),
+            ("é-{nope}", "unknown placeholder {nope} at column 3"),
+            ("{{}}{5-2}", "range {5-2} at column 5 has min above max"),
+            ("ab{digit:0}", "length in {digit:0} at column 3 must be 1 to 256"),
+        ];
+        for (template, expected) in cases {
+            match parse(template) {
+                Ok(_) => panic!("{} should not parse", template),
+                Err(error) => assert_eq!(error, expected, "{}", template),
+            }
+        }
+        let Err(error) = parse("{digit:x}") else {
+            panic!("{{digit:x}} should not parse");
+        };
+        assert!(error.starts_with("bad length in {digit:x} at column 1"), "{}", error);
+    }
+}
@@ -0,0 +1,39 @@
+use rand::RngCore;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{encoding::Encoding, parse_or_default, prompt, shamir, State};
+
+pub const DEFAULT_BYTES: usize = 32;
+const MAX_TUI_BYTES: usize = 1024;
+
+pub fn random_bytes(rng: &mut ChaCha20Rng, count: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; count];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn generate_token(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let count = parse_or_default(&prompt(terminal, state, "Number of bytes (blank for 32)")?, DEFAULT_BYTES, 1..=MAX_TUI_BYTES, "bytes")?;
+
+    let input = prompt(terminal, state, "Encoding (hex, base64, base64url, base32, crockford, base58, z85, all)")?;
+    let show_all = matches!(input.trim(), "" | "all");
+    let encodings = if show_all { Encoding::ALL.to_vec() } else { vec![Encoding::parse(&input)?] };
+
+    let bytes = random_bytes(rng, count);
+    for encoding in encodings {
+        match encoding.encode(&bytes) {
+            Ok(encoded) => state.push_message_output(format!("{}: {}", encoding.name(), encoded)),
+            Err(_) if show_all => {} // z85 is picky about length, skip it quietly when showing every encoding
+            Err(error) => return Err(error),
+        }
+    }
+    state.push_message_output(format!("{} bytes = {} bits of entropy", count, count * 8));
+    shamir::offer_split(terminal, state, rng, &bytes)
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_token(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
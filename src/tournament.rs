@@ -0,0 +1,470 @@
+use std::io::Write;
+
+use rand::{seq::SliceRandom, Rng};
+use rand_chacha::ChaCha20Rng;
+use ratatui::{text::Line, DefaultTerminal};
+
+use crate::{cli, fits_output_pane, prompt, State};
+
+const MAX_PARTICIPANTS: usize = 128;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Single,
+    Double,
+    RoundRobin,
+}
+
+// who fills one side of a match, later rounds refer back to earlier match numbers
+#[derive(Clone)]
+pub enum Slot {
+    Player(String),
+    Bye,
+    Winner(usize),
+    Loser(usize),
+}
+
+pub struct Match {
+    pub number: usize,
+    pub round: String,
+    pub home: Slot,
+    pub away: Slot,
+}
+
+pub struct Tournament {
+    pub format: Format,
+    // first round slots in bracket order, only used to draw the single elimination tree
+    pub seeded: Vec<Slot>,
+    pub matches: Vec<Match>,
+}
+
+impl Format {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "" | "single" => Ok(Format::Single),
+            "double" => Ok(Format::Double),
+            "roundrobin" | "round-robin" | "rr" => Ok(Format::RoundRobin),
+            other => Err(format!("unknown format \"{}\", expected single, double or roundrobin", other)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Format::Single => "single elimination",
+            Format::Double => "double elimination",
+            Format::RoundRobin => "round robin",
+        }
+    }
+}
+
+impl Slot {
+    pub fn label(&self) -> String {
+        match self {
+            Slot::Player(name) => name.clone(),
+            Slot::Bye => String::from("BYE"),
+            Slot::Winner(number) => format!("Winner of M{}", number),
+            Slot::Loser(number) => format!("Loser of M{}", number),
+        }
+    }
+}
+
+// a comma separated list, or the path of a file with one name per line
+pub fn parse_participants(input: &str) -> Result<Vec<String>, String> {
+    let input = input.trim();
+    let names: Vec<String> = match std::fs::read_to_string(input) {
+        Ok(contents) => contents.lines().map(|line| line.trim().to_string()).collect(),
+        Err(_) => input.split(',').map(|name| name.trim().to_string()).collect(),
+    };
+    let names: Vec<String> = names.into_iter().filter(|name| !name.is_empty()).collect();
+    if names.len() < 2 || names.len() > MAX_PARTICIPANTS {
+        return Err(format!("a tournament needs between 2 and {} participants", MAX_PARTICIPANTS));
+    }
+    for (index, name) in names.iter().enumerate() {
+        if names[..index].contains(name) {
+            return Err(format!("\"{}\" is listed twice", name));
+        }
+    }
+    Ok(names)
+}
+
+// standard bracket order for seeds 1..=size, so 1 meets size, 2 meets size - 1 and the top
+// seeds only meet in the late rounds
+fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let total = order.len() * 2 + 1;
+        order = order.iter().flat_map(|seed| [*seed, total - seed]).collect();
+    }
+    order
+}
+
+struct Builder {
+    matches: Vec<Match>,
+}
+
+impl Builder {
+    // a bye means the other side goes through without a match being played
+    fn play(&mut self, round: &str, home: Slot, away: Slot) -> (Slot, Slot) {
+        match (&home, &away) {
+            (Slot::Bye, _) => (away, Slot::Bye),
+            (_, Slot::Bye) => (home, Slot::Bye),
+            _ => {
+                let number = self.matches.len() + 1;
+                self.matches.push(Match { number, round: round.to_string(), home, away });
+                (Slot::Winner(number), Slot::Loser(number))
+            }
+        }
+    }
+
+    fn pair_up(&mut self, round: &str, slots: Vec<Slot>) -> (Vec<Slot>, Vec<Slot>) {
+        if slots.len() == 1 {
+            return (slots, Vec::new());
+        }
+        slots.chunks(2).map(|pair| self.play(round, pair[0].clone(), pair[1].clone())).unzip()
+    }
+}
+
+fn round_name(slots: usize, round: usize) -> String {
+    match slots {
+        2 => String::from("Final"),
+        4 => String::from("Semifinals"),
+        8 => String::from("Quarterfinals"),
+        _ => format!("Round {}", round),
+    }
+}
+
+// participants are shuffled into random seeds, byes go to the top seeds
+fn elimination(rng: &mut ChaCha20Rng, mut names: Vec<String>, format: Format) -> Tournament {
+    names.shuffle(rng);
+    let size = names.len().next_power_of_two();
+    let seeded: Vec<Slot> = bracket_order(size).into_iter()
+        .map(|seed| names.get(seed - 1).map(|name| Slot::Player(name.clone())).unwrap_or(Slot::Bye))
+        .collect();
+
+    let mut builder = Builder { matches: Vec::new() };
+    let mut slots = seeded.clone();
+    let mut losers_by_round = Vec::new();
+    let mut round = 1;
+    while slots.len() > 1 {
+        let name = match format {
+            Format::Double => format!("Winners round {}", round),
+            _ => round_name(slots.len(), round),
+        };
+        let (winners, losers) = builder.pair_up(&name, slots);
+        slots = winners;
+        losers_by_round.push(losers);
+        round += 1;
+    }
+
+    if format == Format::Double {
+        // losers bracket: the first round losers play each other, then every later winners
+        // round drops its losers in, reversed every other time to put off rematches
+        let mut losers_by_round = losers_by_round.into_iter();
+        let (mut current, _) = builder.pair_up("Losers round 1", losers_by_round.next().unwrap_or_default());
+        let mut round = 2;
+        for (index, mut dropped) in losers_by_round.enumerate() {
+            if index % 2 == 0 {
+                dropped.reverse();
+            }
+            let name = format!("Losers round {}", round);
+            current = current.into_iter().zip(dropped).map(|(home, away)| builder.play(&name, home, away).0).collect();
+            round += 1;
+            if current.len() > 1 {
+                current = builder.pair_up(&format!("Losers round {}", round), current).0;
+                round += 1;
+            }
+        }
+        let losers_champion = current.pop().unwrap_or(Slot::Bye);
+        let winners_champion = slots.pop().unwrap_or(Slot::Bye);
+        if let (Slot::Winner(number), Slot::Loser(_)) = builder.play("Grand final", winners_champion, losers_champion) {
+            builder.play("Grand final reset, only if the losers bracket side wins", Slot::Winner(number), Slot::Loser(number));
+        }
+
+        // byes can empty whole losers rounds, renumber the ones that have matches
+        let mut renamed: Vec<String> = Vec::new();
+        for game in builder.matches.iter_mut().filter(|game| game.round.starts_with("Losers round")) {
+            if !renamed.contains(&game.round) {
+                renamed.push(game.round.clone());
+            }
+            let position = renamed.iter().position(|round| *round == game.round).unwrap_or(0);
+            game.round = format!("Losers round {}", position + 1);
+        }
+    }
+    Tournament { format, seeded, matches: builder.matches }
+}
+
+// circle method: one player stays put and the rest rotate, an odd field gets a bye each round
+fn round_robin(rng: &mut ChaCha20Rng, mut names: Vec<String>) -> Tournament {
+    names.shuffle(rng);
+    let mut slots: Vec<Slot> = names.into_iter().map(Slot::Player).collect();
+    if slots.len() % 2 == 1 {
+        slots.push(Slot::Bye);
+    }
+    let count = slots.len();
+    let mut rounds: Vec<Vec<(Slot, Slot)>> = (0..count - 1)
+        .map(|_| {
+            let pairs = (0..count / 2).map(|index| (slots[index].clone(), slots[count - 1 - index].clone())).collect();
+            slots[1..].rotate_right(1);
+            pairs
+        })
+        .collect();
+    rounds.shuffle(rng);
+
+    // byes are kept as matches numbered 0 so the player sitting out still shows in the round
+    let mut matches = Vec::new();
+    let mut number = 0;
+    for (round, mut pairs) in rounds.into_iter().enumerate() {
+        pairs.sort_by_key(|(home, away)| matches!(home, Slot::Bye) || matches!(away, Slot::Bye));
+        for (home, away) in pairs {
+            let (home, away, number) = match (home, away) {
+                (Slot::Bye, player) | (player, Slot::Bye) => (player, Slot::Bye, 0),
+                (home, away) => {
+                    number += 1;
+                    if rng.random_bool(0.5) { (away, home, number) } else { (home, away, number) }
+                }
+            };
+            matches.push(Match { number, round: format!("Round {}", round + 1), home, away });
+        }
+    }
+    Tournament { format: Format::RoundRobin, seeded: Vec::new(), matches }
+}
+
+pub fn generate(rng: &mut ChaCha20Rng, names: Vec<String>, format: Format) -> Tournament {
+    match format {
+        Format::RoundRobin => round_robin(rng, names),
+        _ => elimination(rng, names, format),
+    }
+}
+
+impl Tournament {
+    // matches grouped by round in the order they were created
+    fn rounds(&self) -> Vec<(&str, Vec<&Match>)> {
+        let mut rounds: Vec<(&str, Vec<&Match>)> = Vec::new();
+        for game in &self.matches {
+            match rounds.last_mut() {
+                Some((round, games)) if *round == game.round => games.push(game),
+                _ => rounds.push((&game.round, vec![game])),
+            }
+        }
+        rounds
+    }
+
+    // single elimination as a left to right tree, first round names in the left column
+    pub fn tree(&self) -> Vec<String> {
+        let size = self.seeded.len();
+        let labels: Vec<String> = self.seeded.iter().map(Slot::label).collect();
+        let width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let columns = size.trailing_zeros() as usize + 1;
+        let column_width = width + 4;
+        let mut grid = vec![vec![' '; columns * column_width + 10]; size * 2 - 1];
+
+        for column in 0..columns {
+            let entries = size >> column;
+            let x = column * column_width;
+            for entry in 0..entries {
+                let row = ((1 << column) - 1) + entry * (2 << column);
+                let text = match column {
+                    0 => format!("{:<width$} ─", labels.get(entry).map(String::as_str).unwrap_or_default()),
+                    _ if column + 1 == columns => String::from("─ Champion"),
+                    _ => "─".repeat(width + 2),
+                };
+                for (offset, character) in text.chars().enumerate() {
+                    grid[row][x + offset] = character;
+                }
+                if column + 1 == columns {
+                    continue;
+                }
+                let connector = x + width + 2;
+                if entry % 2 == 0 {
+                    grid[row][connector] = '┐';
+                    let partner = row + (2 << column);
+                    for line in grid.iter_mut().take(partner).skip(row + 1) {
+                        line[connector] = '│';
+                    }
+                    let middle = (row + partner) / 2;
+                    grid[middle][connector] = '├';
+                    grid[middle][connector + 1] = '─';
+                } else {
+                    grid[row][connector] = '┘';
+                }
+            }
+        }
+        grid.iter().map(|line| line.iter().collect::<String>().trim_end().to_string()).collect()
+    }
+
+    pub fn table(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (round, games) in self.rounds() {
+            lines.push(round.to_string());
+            for game in games {
+                let line = match game.away {
+                    Slot::Bye => format!("  {} sits out", game.home.label()),
+                    _ => format!("  M{:<3} {} vs {}", game.number, game.home.label(), game.away.label()),
+                };
+                lines.push(line);
+            }
+        }
+        lines
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut output = format!("# Tournament ({})\n", self.format.name());
+        if self.format == Format::Single {
+            output.push_str(&format!("\n```\n{}\n```\n", self.tree().join("\n")));
+        }
+        for (round, games) in self.rounds() {
+            output.push_str(&format!("\n## {}\n\n| Match | Home | Away |\n| --- | --- | --- |\n", round));
+            for game in games {
+                let escape = |slot: &Slot| slot.label().replace('|', "\\|");
+                let number = if game.number == 0 { String::from("bye") } else { format!("M{}", game.number) };
+                output.push_str(&format!("| {} | {} | {} |\n", number, escape(&game.home), escape(&game.away)));
+            }
+        }
+        output
+    }
+}
+
+fn generate_tournament(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let names = parse_participants(&prompt(terminal, state, "Participants, comma separated or a file with one per line")?)?;
+    let format = Format::parse(&prompt(terminal, state, "Format: single, double, roundrobin (blank for single)")?)?;
+    let path = prompt(terminal, state, "Export Markdown to file (blank to skip)")?;
+
+    let count = names.len();
+    let tournament = generate(rng, names, format);
+    if !path.trim().is_empty() {
+        let mut output = cli::open_output(Some(path.trim()))?;
+        output.write_all(tournament.markdown().as_bytes()).and_then(|_| output.flush()).map_err(|e| e.to_string())?;
+    }
+
+    let saved = if path.trim().is_empty() { String::new() } else { format!(", saved to {}", path.trim()) };
+    let played = tournament.matches.iter().filter(|game| !matches!(game.away, Slot::Bye)).count();
+    state.push_message_output(format!("{} for {} participants, {} matches{}", format.name(), count, played, saved));
+    let lines = if format == Format::Single { tournament.tree() } else { tournament.table() };
+    let lines: Vec<Line<'static>> = lines.into_iter().map(Line::from).collect();
+    // big brackets run to hundreds of lines, those are read from the Markdown export
+    match fits_output_pane(terminal, &lines) {
+        Ok(()) => state.push_output_lines(lines),
+        Err(size) if saved.is_empty() => return Err(format!("the bracket is {}, export it to Markdown to see all of it", size)),
+        Err(size) => state.push_message_output(format!("the bracket is {}, open {} to see all of it", size, path.trim())),
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_tournament(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn players(count: usize) -> Vec<String> {
+        (1..=count).map(|index| format!("P{}", index)).collect()
+    }
+
+    fn slots(game: &Match) -> [&Slot; 2] {
+        [&game.home, &game.away]
+    }
+
+    #[test]
+    fn bracket_order_keeps_top_seeds_apart() {
+        assert_eq!(bracket_order(2), [1, 2]);
+        assert_eq!(bracket_order(8), [1, 8, 4, 5, 2, 7, 3, 6]);
+    }
+
+    #[test]
+    fn single_elimination_plays_everyone_once_into_one_winner() {
+        let mut rng = ChaCha20Rng::seed_from_u64(5);
+        for count in 2..=40 {
+            let tournament = generate(&mut rng, players(count), Format::Single);
+            assert_eq!(tournament.matches.len(), count - 1, "{} players", count);
+            let entered = tournament.matches.iter().flat_map(slots).filter(|slot| matches!(slot, Slot::Player(_))).count();
+            assert_eq!(entered, count, "{} players", count);
+            assert!(tournament.matches.iter().flat_map(slots).all(|slot| !matches!(slot, Slot::Bye | Slot::Loser(_))));
+        }
+    }
+
+    // byes skip matches in both brackets and empty whole losers rounds, every field size still
+    // has to route each player and each dropped loser exactly once
+    #[test]
+    fn double_elimination_with_byes() {
+        let mut rng = ChaCha20Rng::seed_from_u64(6);
+        for count in 2..=40 {
+            let tournament = generate(&mut rng, players(count), Format::Double);
+            let matches = &tournament.matches;
+            // everyone loses twice except the champion, plus the grand final reset
+            assert_eq!(matches.len(), 2 * count - 1, "{} players", count);
+
+            let mut names: Vec<&str> = matches.iter().flat_map(slots)
+                .filter_map(|slot| match slot {
+                    Slot::Player(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            names.sort_unstable();
+            let mut expected = players(count);
+            expected.sort_unstable();
+            assert_eq!(names, expected, "{} players", count);
+
+            let reset = matches.len();
+            for game in matches {
+                let number = game.number;
+                let winner_used = matches.iter().flat_map(slots).filter(|slot| matches!(slot, Slot::Winner(n) if *n == number)).count();
+                assert_eq!(winner_used, usize::from(number != reset), "{} players, winner of M{}", count, number);
+                // losers of winners bracket matches and the grand final play on, losers bracket losers are out
+                let loser_used = matches.iter().flat_map(slots).filter(|slot| matches!(slot, Slot::Loser(n) if *n == number)).count();
+                let drops_down = !game.round.starts_with("Losers round") && number != reset;
+                assert_eq!(loser_used, usize::from(drops_down), "{} players, loser of M{}", count, number);
+                for slot in slots(game) {
+                    assert!(!matches!(slot, Slot::Bye), "{} players, M{} has a bye", count, number);
+                    if let Slot::Winner(earlier) | Slot::Loser(earlier) = slot {
+                        assert!(*earlier < number, "{} players, M{} refers forward", count, number);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_robin_pairs_everyone_once() {
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        for count in 2..=12 {
+            let tournament = generate(&mut rng, players(count), Format::RoundRobin);
+            let mut pairs: Vec<(String, String)> = tournament.matches.iter()
+                .filter(|game| !matches!(game.away, Slot::Bye))
+                .map(|game| {
+                    let (home, away) = (game.home.label(), game.away.label());
+                    if home < away { (home, away) } else { (away, home) }
+                })
+                .collect();
+            pairs.sort();
+            pairs.dedup();
+            assert_eq!(pairs.len(), count * (count - 1) / 2, "{} players", count);
+            let rounds = tournament.rounds().len();
+            assert_eq!(rounds, if count % 2 == 0 { count - 1 } else { count }, "{} players", count);
+        }
+    }
+
+    #[test]
+    fn tree_draws_the_bracket() {
+        let tournament = Tournament {
+            format: Format::Single,
+            seeded: ["A", "B", "C", "D"].map(|name| Slot::Player(name.to_string())).to_vec(),
+            matches: Vec::new(),
+        };
+        assert_eq!(tournament.tree(), [
+            "A ─┐",
+            "   ├────┐",
+            "B ─┘    │",
+            "        ├── Champion",
+            "C ─┐    │",
+            "   ├────┘",
+            "D ─┘",
+        ]);
+        let mut rng = ChaCha20Rng::seed_from_u64(8);
+        assert_eq!(generate(&mut rng, players(128), Format::Single).tree().len(), 255);
+    }
+}
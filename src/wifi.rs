@@ -0,0 +1,173 @@
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use ratatui::DefaultTerminal;
+
+use crate::{prompt, qr, words, State};
+
+// WPA2/WPA3 personal passphrases are 8 to 63 printable ASCII characters
+const MIN_PASSPHRASE: usize = 8;
+const MAX_PASSPHRASE: usize = 63;
+const MAX_SSID_BYTES: usize = 32;
+// letters and digits only so guests can still type it, minus look-alikes (0/O, 1/l/I)
+const CHARACTER_ALPHABET: &[u8] = b"abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+pub enum Security {
+    Wpa2,
+    Wpa3,
+}
+
+pub enum Mode {
+    Words(usize),
+    Characters(usize),
+}
+
+// backslash escapes the characters that are special in the WIFI: payload
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(character, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+// WPA3 networks still use T:WPA, phones negotiate SAE on their own
+pub fn join_payload(ssid: &str, passphrase: &str) -> String {
+    format!("WIFI:T:WPA;S:{};P:{};;", escape(ssid), escape(passphrase))
+}
+
+// the counts that always land inside the WPA length bounds, whichever words get picked
+fn check_mode(mode: &Mode) -> Result<(), String> {
+    let (amount, min, max, unit) = match *mode {
+        Mode::Words(count) => {
+            let lengths = words::wordlist.iter().map(|word| word.len());
+            let (shortest, longest) = (lengths.clone().min().unwrap_or(1), lengths.max().unwrap_or(1));
+            // every word brings a '-', and the digit on the end is one more character
+            let min = (MIN_PASSPHRASE - 1).div_ceil(shortest + 1);
+            let max = (MAX_PASSPHRASE - 1) / (longest + 1);
+            (count, min, max, "words")
+        }
+        Mode::Characters(length) => (length, MIN_PASSPHRASE, MAX_PASSPHRASE, "characters"),
+    };
+    if !(min..=max).contains(&amount) {
+        return Err(format!("WPA passphrases need {} to {} {}", min, max, unit));
+    }
+    Ok(())
+}
+
+// returns the passphrase and its entropy in bits
+pub fn generate(rng: &mut ChaCha20Rng, mode: &Mode) -> Result<(String, f64), String> {
+    check_mode(mode)?;
+    let (passphrase, bits) = match *mode {
+        Mode::Words(count) => {
+            // words joined with '-' plus one digit, easy to read off a card on the fridge
+            let mut parts: Vec<String> = (0..count)
+                .map(|_| words::wordlist[rng.random_range(0..words::wordlist.len())].to_string())
+                .collect();
+            let digit = rng.random_range(0..10);
+            parts.push(digit.to_string());
+            let bits = count as f64 * (words::wordlist.len() as f64).log2() + 10f64.log2();
+            (parts.join("-"), bits)
+        }
+        Mode::Characters(length) => {
+            let passphrase = (0..length)
+                .map(|_| CHARACTER_ALPHABET[rng.random_range(0..CHARACTER_ALPHABET.len())] as char)
+                .collect();
+            (passphrase, length as f64 * (CHARACTER_ALPHABET.len() as f64).log2())
+        }
+    };
+    Ok((passphrase, bits))
+}
+
+fn generate_credentials(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) -> Result<(), String> {
+    let ssid = prompt(terminal, state, "Network name (SSID)")?;
+    if ssid.is_empty() || ssid.len() > MAX_SSID_BYTES {
+        return Err(format!("SSID must be 1 to {} bytes", MAX_SSID_BYTES));
+    }
+
+    let input = prompt(terminal, state, "Security (wpa2 or wpa3, blank for wpa2)")?;
+    let security = match input.trim().to_lowercase().as_str() {
+        "" | "wpa2" => Security::Wpa2,
+        "wpa3" => Security::Wpa3,
+        other => return Err(format!("unknown security \"{}\"", other)),
+    };
+
+    let input = prompt(terminal, state, "Mode: words N or chars N (blank for words 4)")?;
+    let mut parts = input.split_whitespace();
+    let kind = parts.next().unwrap_or("words").to_lowercase();
+    let amount = match parts.next() {
+        Some(value) => Some(value.parse::<usize>().map_err(|e| e.to_string())?),
+        None => None,
+    };
+    let mode = match kind.as_str() {
+        "words" => Mode::Words(amount.unwrap_or(4)),
+        "chars" | "characters" => Mode::Characters(amount.unwrap_or(20)),
+        other => return Err(format!("unknown mode \"{}\"", other)),
+    };
+
+    let (passphrase, bits) = generate(rng, &mode)?;
+    let lines = qr::render(&join_payload(&ssid, &passphrase))?;
+
+    let security = match security {
+        Security::Wpa2 => "WPA2",
+        Security::Wpa3 => "WPA3",
+    };
+    state.push_message_output(format!("{} network \"{}\"", security, ssid));
+    state.push_message_output(format!("passphrase: {} ({:.1} bits)", passphrase, bits));
+    match qr::fits_output_pane(terminal, &lines) {
+        Ok(()) => state.push_output_lines(lines),
+        Err(error) => state.push_message_output(error),
+    }
+    Ok(())
+}
+
+pub fn run(terminal: &mut DefaultTerminal, state: &mut State, rng: &mut ChaCha20Rng) {
+    if let Err(error) = generate_credentials(terminal, state, rng) {
+        state.push_error_output(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn escapes_payload_specials() {
+        assert_eq!(escape(r#"a\b;c,d:e"f"#), r#"a\\b\;c\,d\:e\"f"#);
+        assert_eq!(escape("plain name"), "plain name");
+        assert_eq!(join_payload("home;net", "p:w\"d"), r#"WIFI:T:WPA;S:home\;net;P:p\:w\"d;;"#);
+    }
+
+    #[test]
+    fn character_lengths_follow_wpa_bounds() {
+        assert!(check_mode(&Mode::Characters(7)).is_err());
+        assert!(check_mode(&Mode::Characters(8)).is_ok());
+        assert!(check_mode(&Mode::Characters(63)).is_ok());
+        assert!(check_mode(&Mode::Characters(64)).is_err());
+    }
+
+    #[test]
+    fn accepted_word_counts_always_fit() {
+        let accepted: Vec<usize> = (0..=20).filter(|count| check_mode(&Mode::Words(*count)).is_ok()).collect();
+        let (min, max) = (accepted[0], accepted[accepted.len() - 1]);
+        assert_eq!(accepted, (min..=max).collect::<Vec<_>>());
+
+        // worst cases: every word the shortest or the longest in the list, each followed by a '-'
+        // and the digit at the end
+        let lengths = words::wordlist.iter().map(|word| word.len());
+        let (shortest, longest) = (lengths.clone().min().unwrap(), lengths.max().unwrap());
+        let (shortest_passphrase, longest_passphrase) = (min * (shortest + 1) + 1, max * (longest + 1) + 1);
+        assert!(shortest_passphrase >= MIN_PASSPHRASE && longest_passphrase <= MAX_PASSPHRASE);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+        for count in [min, max] {
+            for _ in 0..200 {
+                let (passphrase, _) = generate(&mut rng, &Mode::Words(count)).unwrap();
+                assert!((MIN_PASSPHRASE..=MAX_PASSPHRASE).contains(&passphrase.len()), "{}", passphrase);
+            }
+        }
+    }
+}
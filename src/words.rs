@@ -1,4 +1,5 @@
 // hardocing this vec mostly cause we want this to be one single runnable bin
+#[allow(non_upper_case_globals)]
 pub static wordlist: &[&str] = &[
     "actor", "alarm", "apple", "award", "baker", "beach", "bench", "black", "blank", "blast", "blend", "blink", "block", "blood", "bloom", "blush",
     "board", "boost", "booth", "border", "bored", "braid", "brain", "brand", "brass", "brave", "bread", "break", "breeze", "brick", "brief", "bright",